@@ -0,0 +1,45 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+pub const ARENA_WIDTH: f32 = 1200.0;
+pub const ARENA_HEIGHT: f32 = 700.0;
+const WALL_THICKNESS: f32 = 20.0;
+
+// Marker for static level geometry that blocks movement and catches thrown swords
+#[derive(Component)]
+pub struct Wall;
+
+pub struct ArenaPlugin;
+
+impl Plugin for ArenaPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_arena);
+    }
+}
+
+fn spawn_arena(mut commands: Commands) {
+    let half_width = ARENA_WIDTH / 2.0;
+    let half_height = ARENA_HEIGHT / 2.0;
+
+    // (center, half extents) of the four boundary walls
+    let walls = [
+        (Vec2::new(0.0, half_height), Vec2::new(half_width + WALL_THICKNESS, WALL_THICKNESS / 2.0)),
+        (Vec2::new(0.0, -half_height), Vec2::new(half_width + WALL_THICKNESS, WALL_THICKNESS / 2.0)),
+        (Vec2::new(-half_width, 0.0), Vec2::new(WALL_THICKNESS / 2.0, half_height)),
+        (Vec2::new(half_width, 0.0), Vec2::new(WALL_THICKNESS / 2.0, half_height)),
+    ];
+
+    for (center, half_extents) in walls {
+        commands.spawn((
+            Sprite {
+                color: Color::srgb(0.3, 0.3, 0.35),
+                custom_size: Some(half_extents * 2.0),
+                ..default()
+            },
+            Transform::from_xyz(center.x, center.y, -1.0),
+            RigidBody::Fixed,
+            Collider::cuboid(half_extents.x, half_extents.y),
+            Wall,
+        ));
+    }
+}
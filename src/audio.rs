@@ -0,0 +1,371 @@
+use crate::damage::DamageEvent;
+use crate::effects::Dying;
+use crate::enemy::{WaveState, BRUTE_SOUNDS, SHOOTER_SOUNDS};
+use crate::input_map::Rebinding;
+use crate::parry::PARRY_SOUND;
+use crate::player::PlayerHealth;
+use crate::swing_animation::{SwingAnimation, SwingNotifyEvent, SwingType, WHOOSH};
+use crate::sword_throw::EMBED_SOUND;
+use crate::weapon::SWAP_SOUND;
+use bevy::asset::io::file::FileAssetReader;
+use bevy::audio::Volume;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+pub const AUDIO_SETTINGS_PATH: &str = "config/audio_settings.ron";
+const MUSIC_CROSSFADE_TIME: f32 = 1.5; // Seconds for one track to fade fully in or out
+const VOLUME_STEP: f32 = 0.1; // Per press of a volume button in the menu
+
+const PLAYER_HURT_SOUND: &str = "audio/player_hurt.ogg";
+const CRIT_SOUND: &str = "audio/crit.ogg";
+const SWEET_SPOT_SOUND: &str = "audio/sweet_spot_ring.ogg";
+const WAVE_START_SOUND: &str = "audio/wave_start.ogg";
+const MENU_MUSIC: &str = "audio/music_menu.ogg";
+const GAMEPLAY_MUSIC: &str = "audio/music_gameplay.ogg";
+
+pub struct GameAudioPlugin;
+
+impl Plugin for GameAudioPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(AudioSettings::load_or_default(AUDIO_SETTINGS_PATH))
+            .init_resource::<AvailableSounds>()
+            .add_event::<SoundEvent>()
+            .add_systems(Startup, (check_sounds, spawn_music).chain())
+            .add_systems(
+                Update,
+                (
+                    (swing_sounds, damage_sounds, death_sounds, wave_sounds),
+                    play_sounds,
+                )
+                    .chain(),
+            )
+            .add_systems(Update, crossfade_music);
+    }
+}
+
+// Linear volume multipliers, loaded from AUDIO_SETTINGS_PATH when present
+#[derive(Resource, Clone, Serialize, Deserialize)]
+pub struct AudioSettings {
+    pub master: f32,
+    pub music: f32,
+    pub sfx: f32,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self {
+            master: 1.0,
+            music: 0.6,
+            sfx: 0.8,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum VolumeChannel {
+    Master,
+    Music,
+    Sfx,
+}
+
+impl VolumeChannel {
+    pub const ALL: [VolumeChannel; 3] =
+        [VolumeChannel::Master, VolumeChannel::Music, VolumeChannel::Sfx];
+}
+
+impl AudioSettings {
+    pub fn volume(&self, channel: VolumeChannel) -> f32 {
+        match channel {
+            VolumeChannel::Master => self.master,
+            VolumeChannel::Music => self.music,
+            VolumeChannel::Sfx => self.sfx,
+        }
+    }
+
+    // Moves a channel up or down by `steps` of VOLUME_STEP, within 0.0 - 1.0
+    pub fn adjust(&mut self, channel: VolumeChannel, steps: i32) {
+        let volume = match channel {
+            VolumeChannel::Master => &mut self.master,
+            VolumeChannel::Music => &mut self.music,
+            VolumeChannel::Sfx => &mut self.sfx,
+        };
+        // Rounded so repeated steps land on whole percentages
+        let percent = ((*volume + steps as f32 * VOLUME_STEP) * 100.0).round();
+        *volume = percent.clamp(0.0, 100.0) / 100.0;
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let text = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        ron::from_str(&text).map_err(|e| e.to_string())
+    }
+
+    pub fn load_or_default(path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref();
+        if !path.exists() {
+            return AudioSettings::default();
+        }
+        AudioSettings::load(path).unwrap_or_else(|error| {
+            let path = path.display();
+            warn!(target: "audio", "Failed to load audio settings from {}: {}", path, error);
+            AudioSettings::default()
+        })
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), String> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let text = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|e| e.to_string())?;
+        fs::write(path, text).map_err(|e| e.to_string())
+    }
+}
+
+// Every sound the game plays, checked against the assets folder on startup
+fn known_sounds() -> Vec<&'static str> {
+    let mut sounds = vec![
+        PLAYER_HURT_SOUND,
+        CRIT_SOUND,
+        SWEET_SPOT_SOUND,
+        WAVE_START_SOUND,
+        MENU_MUSIC,
+        GAMEPLAY_MUSIC,
+        SWAP_SOUND,
+        EMBED_SOUND,
+        PARRY_SOUND,
+    ];
+    for enemy in [SHOOTER_SOUNDS, BRUTE_SOUNDS] {
+        sounds.extend([enemy.impact, enemy.death]);
+    }
+    sounds.extend(SwingType::ALL.map(|swing_type| swing_type.whoosh_sound()));
+    sounds
+}
+
+// Which sound files are actually in the assets folder. Sounds that haven't been added yet
+// are skipped, rather than failing to load every time they'd play.
+#[derive(Resource, Default)]
+struct AvailableSounds(HashMap<&'static str, bool>);
+
+impl AvailableSounds {
+    fn exists(sound: &str) -> bool {
+        FileAssetReader::get_base_path().join("assets").join(sound).exists()
+    }
+
+    fn contains(&mut self, sound: &'static str) -> bool {
+        // Known sounds were all checked on startup; anything else is checked on first use
+        *self.0.entry(sound).or_insert_with(|| {
+            let exists = Self::exists(sound);
+            if !exists {
+                warn!(target: "audio", "Sound {} is missing, it won't be played", sound);
+            }
+            exists
+        })
+    }
+}
+
+// One warning naming every missing sound, rather than the game going quietly silent
+fn check_sounds(mut available: ResMut<AvailableSounds>) {
+    let sounds = known_sounds();
+    let mut missing = Vec::new();
+    for sound in sounds.iter().copied() {
+        let exists = AvailableSounds::exists(sound);
+        if !exists {
+            missing.push(sound);
+        }
+        available.0.insert(sound, exists);
+    }
+    if !missing.is_empty() {
+        warn!(
+            target: "audio",
+            "{} of {} sounds are missing from assets/ and won't be played: {}",
+            missing.len(),
+            sounds.len(),
+            missing.join(", ")
+        );
+    }
+}
+
+// Request a one-shot sound effect; any system can write these
+#[derive(Event, Clone)]
+pub struct SoundEvent {
+    pub sound: &'static str, // Asset path
+    pub volume: f32,
+    pub speed: f32, // Playback rate, which also shifts the pitch
+}
+
+impl SoundEvent {
+    pub fn new(sound: &'static str) -> Self {
+        Self {
+            sound,
+            volume: 1.0,
+            speed: 1.0,
+        }
+    }
+
+    pub fn with_volume(mut self, volume: f32) -> Self {
+        self.volume = volume;
+        self
+    }
+
+    pub fn with_speed(mut self, speed: f32) -> Self {
+        self.speed = speed;
+        self
+    }
+}
+
+// Sounds of an enemy archetype, assigned when the enemy is spawned
+#[derive(Component, Clone)]
+pub struct EnemySounds {
+    pub impact: &'static str, // Played when it takes a hit
+    pub death: &'static str,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum MusicKind {
+    Menu,
+    Gameplay,
+}
+
+#[derive(Component)]
+struct MusicTrack {
+    kind: MusicKind,
+    fade: f32, // 0.0 = silent, 1.0 = full music volume
+}
+
+fn spawn_music(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut available: ResMut<AvailableSounds>,
+) {
+    for (kind, path) in [(MusicKind::Menu, MENU_MUSIC), (MusicKind::Gameplay, GAMEPLAY_MUSIC)] {
+        if !available.contains(path) {
+            continue;
+        }
+        // Both tracks loop the whole time; the crossfade only moves their volumes
+        commands.spawn((
+            AudioPlayer::new(asset_server.load(path)),
+            PlaybackSettings::LOOP.with_volume(Volume::Linear(0.0)),
+            MusicTrack { kind, fade: 0.0 },
+        ));
+    }
+}
+
+// Menu music while paused or in the bindings menu, gameplay music otherwise
+fn crossfade_music(
+    time: Res<Time<Real>>, // Keeps fading while the game is paused
+    virtual_time: Res<Time<Virtual>>,
+    rebinding: Res<Rebinding>,
+    settings: Res<AudioSettings>,
+    mut track_query: Query<(&mut MusicTrack, Option<&mut AudioSink>)>,
+) {
+    let current = if virtual_time.is_paused() || rebinding.menu_open {
+        MusicKind::Menu
+    } else {
+        MusicKind::Gameplay
+    };
+    let step = time.delta_secs() / MUSIC_CROSSFADE_TIME;
+
+    for (mut track, sink) in track_query.iter_mut() {
+        let target = if track.kind == current { 1.0 } else { 0.0 };
+        track.fade = if track.fade < target {
+            (track.fade + step).min(target)
+        } else {
+            (track.fade - step).max(target)
+        };
+
+        // The sink only appears once the track has loaded and started
+        if let Some(mut sink) = sink {
+            sink.set_volume(Volume::Linear(track.fade * settings.music * settings.master));
+        }
+    }
+}
+
+fn play_sounds(
+    mut commands: Commands,
+    mut sound_events: EventReader<SoundEvent>,
+    asset_server: Res<AssetServer>,
+    settings: Res<AudioSettings>,
+    mut available: ResMut<AvailableSounds>,
+) {
+    for event in sound_events.read() {
+        if !available.contains(event.sound) {
+            continue;
+        }
+        commands.spawn((
+            AudioPlayer::new(asset_server.load(event.sound)),
+            PlaybackSettings::DESPAWN
+                .with_volume(Volume::Linear(event.volume * settings.sfx * settings.master))
+                .with_speed(event.speed),
+        ));
+    }
+}
+
+// Whoosh where the swing curve puts its notify, higher pitched for faster swings
+fn swing_sounds(
+    mut notify_events: EventReader<SwingNotifyEvent>,
+    swing_query: Query<&SwingAnimation>,
+    mut sound_events: EventWriter<SoundEvent>,
+) {
+    for event in notify_events.read() {
+        if event.name != WHOOSH {
+            continue;
+        }
+        // A swing broken off before its whoosh flushes its notifies without playing through
+        let Ok(swing) = swing_query.get(event.sword) else {
+            continue;
+        };
+        if !swing.is_swinging {
+            continue;
+        }
+        let whoosh = SoundEvent::new(event.swing_type.whoosh_sound());
+        sound_events.write(whoosh.with_speed(swing.speed_scale()));
+    }
+}
+
+fn damage_sounds(
+    mut damage_events: EventReader<DamageEvent>,
+    enemy_query: Query<&EnemySounds>,
+    player_query: Query<(), With<PlayerHealth>>,
+    mut sound_events: EventWriter<SoundEvent>,
+) {
+    for event in damage_events.read() {
+        if let Ok(sounds) = enemy_query.get(event.target) {
+            // Burn and poison ticks are frequent, keep them in the background
+            let volume = if event.indirect { 0.3 } else { 1.0 };
+            sound_events.write(SoundEvent::new(sounds.impact).with_volume(volume));
+            // Layered over the impact; a sweet-spot crit plays both
+            if event.crit {
+                sound_events.write(SoundEvent::new(CRIT_SOUND));
+            }
+            if event.sweet_spot {
+                sound_events.write(SoundEvent::new(SWEET_SPOT_SOUND).with_volume(0.8));
+            }
+        } else if player_query.contains(event.target) {
+            sound_events.write(SoundEvent::new(PLAYER_HURT_SOUND));
+        }
+    }
+}
+
+fn death_sounds(
+    dying_query: Query<&EnemySounds, Added<Dying>>,
+    mut sound_events: EventWriter<SoundEvent>,
+) {
+    for sounds in dying_query.iter() {
+        sound_events.write(SoundEvent::new(sounds.death));
+    }
+}
+
+fn wave_sounds(
+    waves: Res<WaveState>,
+    mut last_wave: Local<u32>,
+    mut sound_events: EventWriter<SoundEvent>,
+) {
+    if waves.wave != *last_wave {
+        *last_wave = waves.wave;
+        sound_events.write(SoundEvent::new(WAVE_START_SOUND));
+    }
+}
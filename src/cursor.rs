@@ -1,3 +1,4 @@
+use crate::input_map::{Action, ActionState};
 use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
 
@@ -18,11 +19,12 @@ fn setup_cursor(mut window_query: Query<&mut Window, With<PrimaryWindow>>) {
 
 fn handle_cursor_toggle(
     mut window_query: Query<&mut Window, With<PrimaryWindow>>,
-    keyboard: Res<ButtonInput<KeyCode>>,
+    actions: Res<ActionState>,
 ) {
-    if keyboard.just_pressed(KeyCode::Tab) {
-        if let Ok(mut window) = window_query.single_mut() {
-            window.cursor_options.visible = !window.cursor_options.visible;
-        }
+    if !actions.just_pressed(Action::ToggleCursor) {
+        return;
+    }
+    if let Ok(mut window) = window_query.single_mut() {
+        window.cursor_options.visible = !window.cursor_options.visible;
     }
 }
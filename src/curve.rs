@@ -0,0 +1,337 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::f32::consts::{PI, TAU};
+
+// Named timing curves mapping 0.0..=1.0 progress onto eased progress. Back and elastic
+// easings overshoot outside 0.0..=1.0 on purpose.
+#[derive(Clone, Copy, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub enum Easing {
+    #[default]
+    Linear,
+    SmoothStep,
+    QuadIn,
+    QuadOut,
+    QuadInOut,
+    CubicIn,
+    CubicOut,
+    #[serde(alias = "EaseInOutCubic")]
+    CubicInOut,
+    ExpoIn,
+    ExpoOut,
+    ExpoInOut,
+    BackIn,
+    BackOut,
+    BackInOut,
+    ElasticIn,
+    ElasticOut,
+    ElasticInOut,
+    CubicBezier(f32, f32, f32, f32), // CSS-style control points (x1, y1) and (x2, y2)
+}
+
+const BACK_OVERSHOOT: f32 = 1.70158;
+const ELASTIC_PERIOD: f32 = TAU / 3.0;
+
+impl Easing {
+    // Every easing without parameters, in the order editors cycle through them
+    pub const NAMED: [Easing; 17] = [
+        Easing::Linear,
+        Easing::SmoothStep,
+        Easing::QuadIn,
+        Easing::QuadOut,
+        Easing::QuadInOut,
+        Easing::CubicIn,
+        Easing::CubicOut,
+        Easing::CubicInOut,
+        Easing::ExpoIn,
+        Easing::ExpoOut,
+        Easing::ExpoInOut,
+        Easing::BackIn,
+        Easing::BackOut,
+        Easing::BackInOut,
+        Easing::ElasticIn,
+        Easing::ElasticOut,
+        Easing::ElasticInOut,
+    ];
+
+    // Input is clamped to 0.0..=1.0; only the output may overshoot
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match *self {
+            Easing::Linear => t,
+            Easing::SmoothStep => t * t * (3.0 - 2.0 * t),
+            Easing::QuadIn => t * t,
+            Easing::QuadOut => 1.0 - (1.0 - t).powi(2),
+            Easing::QuadInOut => in_out(t, |t| t * t),
+            Easing::CubicIn => t.powi(3),
+            Easing::CubicOut => 1.0 - (1.0 - t).powi(3),
+            Easing::CubicInOut => in_out(t, |t| t.powi(3)),
+            Easing::ExpoIn => expo_in(t),
+            Easing::ExpoOut => 1.0 - expo_in(1.0 - t),
+            Easing::ExpoInOut => in_out(t, expo_in),
+            Easing::BackIn => back_in(t),
+            Easing::BackOut => 1.0 - back_in(1.0 - t),
+            Easing::BackInOut => in_out(t, back_in),
+            Easing::ElasticIn => elastic_in(t),
+            Easing::ElasticOut => 1.0 - elastic_in(1.0 - t),
+            Easing::ElasticInOut => in_out(t, elastic_in),
+            Easing::CubicBezier(x1, y1, x2, y2) => cubic_bezier_easing(t, x1, y1, x2, y2),
+        }
+    }
+
+    // The next named easing; a custom cubic-bezier goes back to the start of the list
+    pub fn next(&self) -> Self {
+        let index = Easing::NAMED.iter().position(|e| e == self);
+        index.map_or(Easing::Linear, |i| Easing::NAMED[(i + 1) % Easing::NAMED.len()])
+    }
+}
+
+// Mirror an ease-in into an ease-in-out
+fn in_out(t: f32, ease_in: impl Fn(f32) -> f32) -> f32 {
+    if t < 0.5 {
+        ease_in(t * 2.0) / 2.0
+    } else {
+        1.0 - ease_in((1.0 - t) * 2.0) / 2.0
+    }
+}
+
+fn expo_in(t: f32) -> f32 {
+    if t <= 0.0 {
+        0.0
+    } else {
+        2f32.powf(10.0 * t - 10.0)
+    }
+}
+
+fn back_in(t: f32) -> f32 {
+    (BACK_OVERSHOOT + 1.0) * t.powi(3) - BACK_OVERSHOOT * t * t
+}
+
+fn elastic_in(t: f32) -> f32 {
+    if t <= 0.0 || t >= 1.0 {
+        return t;
+    }
+    -(2f32.powf(10.0 * t - 10.0)) * ((t * 10.0 - 10.75) * ELASTIC_PERIOD).sin()
+}
+
+// Like CSS cubic-bezier(): find the curve parameter whose x is `t`, then return its y
+fn cubic_bezier_easing(t: f32, x1: f32, y1: f32, x2: f32, y2: f32) -> f32 {
+    let bezier = |a: f32, b: f32, s: f32| {
+        let u = 1.0 - s;
+        3.0 * u * u * s * a + 3.0 * u * s * s * b + s * s * s
+    };
+    let slope = |a: f32, b: f32, s: f32| {
+        let u = 1.0 - s;
+        3.0 * u * u * a + 6.0 * u * s * (b - a) + 3.0 * s * s * (1.0 - b)
+    };
+
+    // Newton's method converges in a few steps for well-behaved curves
+    let mut s = t;
+    for _ in 0..8 {
+        let error = bezier(x1, x2, s) - t;
+        if error.abs() < 1e-5 {
+            return bezier(y1, y2, s);
+        }
+        let derivative = slope(x1, x2, s);
+        if derivative.abs() < 1e-6 {
+            break;
+        }
+        s = (s - error / derivative).clamp(0.0, 1.0);
+    }
+
+    // Fall back to bisection where the slope flattens out
+    let (mut low, mut high) = (0.0, 1.0);
+    s = t;
+    for _ in 0..20 {
+        let x = bezier(x1, x2, s);
+        if (x - t).abs() < 1e-5 {
+            break;
+        }
+        if x < t {
+            low = s;
+        } else {
+            high = s;
+        }
+        s = (low + high) / 2.0;
+    }
+    bezier(y1, y2, s)
+}
+
+// How a spline's points are read
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub enum SplineKind {
+    Bezier,     // Cubic segments sharing end points: anchor, handle, handle, anchor, ...
+    CatmullRom, // Passes through every point
+    Hermite,    // Alternating anchor and tangent: anchor, tangent, anchor, tangent, ...
+}
+
+// A composite 2D curve. Every kind is evaluated as a chain of cubic bezier segments, each
+// taking an equal share of the 0.0..=1.0 parameter range.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Spline {
+    pub kind: SplineKind,
+    pub points: Vec<Vec2>,
+}
+
+impl Spline {
+    pub fn new(kind: SplineKind, points: Vec<Vec2>) -> Self {
+        Self { kind, points }
+    }
+
+    pub fn segment_count(&self) -> usize {
+        let count = self.points.len();
+        match self.kind {
+            SplineKind::Bezier => count.saturating_sub(1) / 3,
+            SplineKind::CatmullRom => count.saturating_sub(1),
+            SplineKind::Hermite => (count / 2).saturating_sub(1),
+        }
+    }
+
+    // Bezier control points of one segment
+    fn segment(&self, index: usize) -> [Vec2; 4] {
+        let p = &self.points;
+        match self.kind {
+            SplineKind::Bezier => {
+                let i = index * 3;
+                [p[i], p[i + 1], p[i + 2], p[i + 3]]
+            }
+            SplineKind::CatmullRom => {
+                // End points are repeated so the curve still reaches them
+                let p0 = p[index.saturating_sub(1)];
+                let (p1, p2) = (p[index], p[index + 1]);
+                let p3 = p[(index + 2).min(p.len() - 1)];
+                [p1, p1 + (p2 - p0) / 6.0, p2 - (p3 - p1) / 6.0, p2]
+            }
+            SplineKind::Hermite => {
+                let (p0, m0) = (p[index * 2], p[index * 2 + 1]);
+                let (p1, m1) = (p[index * 2 + 2], p[index * 2 + 3]);
+                [p0, p0 + m0 / 3.0, p1 - m1 / 3.0, p1]
+            }
+        }
+    }
+
+    // Segment index and the parameter within it. Outside 0.0..=1.0 the end segments are
+    // extrapolated, so overshooting easings carry the curve past its ends.
+    fn locate(&self, u: f32) -> Option<(usize, f32)> {
+        let segments = self.segment_count();
+        if segments == 0 {
+            return None;
+        }
+        let scaled = u * segments as f32;
+        let index = (scaled.max(0.0).floor() as usize).min(segments - 1);
+        Some((index, scaled - index as f32))
+    }
+
+    pub fn position(&self, u: f32) -> Vec2 {
+        match self.locate(u) {
+            Some((index, t)) => cubic_bezier(self.segment(index), t),
+            None => self.points.first().copied().unwrap_or(Vec2::ZERO),
+        }
+    }
+
+    // Direction of travel at `u`; not normalized, zero where the curve stalls
+    pub fn tangent(&self, u: f32) -> Vec2 {
+        match self.locate(u) {
+            Some((index, t)) => cubic_bezier_derivative(self.segment(index), t),
+            None => Vec2::ZERO,
+        }
+    }
+
+    // Where a point is shown and dragged in editors. Hermite tangents are shown as handles a
+    // third of the way along them, the same place a bezier handle would sit.
+    pub fn handle(&self, index: usize) -> Vec2 {
+        if self.kind == SplineKind::Hermite && index % 2 == 1 {
+            self.points[index - 1] + self.points[index] / 3.0
+        } else {
+            self.points[index]
+        }
+    }
+
+    pub fn set_handle(&mut self, index: usize, position: Vec2) {
+        if self.kind == SplineKind::Hermite && index % 2 == 1 {
+            self.points[index] = (position - self.points[index - 1]) * 3.0;
+        } else {
+            self.points[index] = position;
+        }
+    }
+
+    // Whether the curve passes through the point, as opposed to being shaped by it
+    pub fn is_anchor(&self, index: usize) -> bool {
+        match self.kind {
+            SplineKind::Bezier => index.is_multiple_of(3),
+            SplineKind::CatmullRom => true,
+            SplineKind::Hermite => index.is_multiple_of(2),
+        }
+    }
+}
+
+// Cumulative length along a spline, for moving along it at a controlled speed rather than at
+// whatever speed its parameterization happens to give
+#[derive(Clone, Debug, Default)]
+pub struct ArcLengthTable {
+    lengths: Vec<f32>, // Length covered at evenly spaced parameter values
+}
+
+impl ArcLengthTable {
+    pub fn new(spline: &Spline, samples: usize) -> Self {
+        let samples = samples.max(1);
+        let mut lengths = Vec::with_capacity(samples + 1);
+        let mut length = 0.0;
+        let mut last = spline.position(0.0);
+        lengths.push(0.0);
+        for i in 1..=samples {
+            let point = spline.position(i as f32 / samples as f32);
+            length += point.distance(last);
+            lengths.push(length);
+            last = point;
+        }
+        Self { lengths }
+    }
+
+    pub fn total(&self) -> f32 {
+        self.lengths.last().copied().unwrap_or(0.0)
+    }
+
+    // Spline parameter at which `fraction` of the total length has been covered.
+    // Overshoot past either end is passed through unchanged.
+    pub fn parameter(&self, fraction: f32) -> f32 {
+        let total = self.total();
+        if total <= 0.0 || !(0.0..=1.0).contains(&fraction) {
+            return fraction;
+        }
+        let target = fraction * total;
+        let index = self.lengths.partition_point(|length| *length < target).max(1);
+        let (before, after) = (self.lengths[index - 1], self.lengths[index]);
+        let within = if after > before {
+            (target - before) / (after - before)
+        } else {
+            0.0
+        };
+        let samples = (self.lengths.len() - 1) as f32;
+        ((index - 1) as f32 + within) / samples
+    }
+}
+
+// B(t) = (1-t)³P₀ + 3(1-t)²tP₁ + 3(1-t)t²P₂ + t³P₃
+pub fn cubic_bezier([p0, p1, p2, p3]: [Vec2; 4], t: f32) -> Vec2 {
+    let u = 1.0 - t;
+    p0 * (u * u * u) + p1 * (3.0 * u * u * t) + p2 * (3.0 * u * t * t) + p3 * (t * t * t)
+}
+
+pub fn cubic_bezier_derivative([p0, p1, p2, p3]: [Vec2; 4], t: f32) -> Vec2 {
+    let u = 1.0 - t;
+    (p1 - p0) * (3.0 * u * u) + (p2 - p1) * (6.0 * u * t) + (p3 - p2) * (3.0 * t * t)
+}
+
+pub fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+// Signed angle from `a` to `b` the short way round, in -PI..=PI
+pub fn angle_difference(a: f32, b: f32) -> f32 {
+    (b - a + PI).rem_euclid(TAU) - PI
+}
+
+// Interpolate between two angles without winding through extra turns
+pub fn lerp_angle(a: f32, b: f32, t: f32) -> f32 {
+    a + angle_difference(a, b) * t
+}
@@ -0,0 +1,286 @@
+use crate::enemy::Enemy;
+use crate::player::PlayerHealth;
+use crate::score::Score;
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+const DAMAGE_NUMBER_LIFETIME: f32 = 0.8;
+const DAMAGE_NUMBER_RISE: f32 = 60.0; // Initial upward drift in px/s
+const DAMAGE_NUMBER_SIZE: f32 = 16.0;
+const CRIT_NUMBER_SIZE: f32 = 26.0;
+const SWEET_SPOT_NUMBER_SIZE: f32 = 20.0;
+const SWEET_SPOT_COLOR: Color = Color::srgb(0.4, 1.0, 0.9);
+
+// Health bars are children of the enemy, so these are in the enemy's local units (1 = diameter)
+const HEALTH_BAR_SIZE: Vec2 = Vec2::new(1.0, 0.12);
+const HEALTH_BAR_OFFSET: f32 = 0.8;
+
+pub struct DamagePlugin;
+
+impl Plugin for DamagePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<DamageEvent>().add_systems(
+            Update,
+            (
+                (
+                    apply_damage,
+                    spawn_damage_numbers,
+                    attach_health_bars,
+                    update_health_bars,
+                )
+                    .chain(),
+                animate_damage_numbers,
+            ),
+        );
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub enum DamageType {
+    #[default]
+    Physical,   // Sword cuts and melee strikes
+    Projectile, // Shots, including reflected ones
+    Fire,       // Sets the target burning
+    Ice,        // Chills the target, slowing it down
+    Lightning,  // Arcs on to enemies nearby
+    Poison,     // Weak but long-lasting, stacks high
+}
+
+impl DamageType {
+    pub fn color(&self) -> Color {
+        match self {
+            DamageType::Physical => Color::WHITE,
+            DamageType::Projectile => Color::srgb(1.0, 0.6, 0.1),
+            DamageType::Fire => Color::srgb(1.0, 0.4, 0.1),
+            DamageType::Ice => Color::srgb(0.5, 0.85, 1.0),
+            DamageType::Lightning => Color::srgb(1.0, 1.0, 0.4),
+            DamageType::Poison => Color::srgb(0.45, 0.9, 0.2),
+        }
+    }
+}
+
+// Share of each damage type an enemy actually takes; 1.0 = normal, 0.0 = immune. Statuses
+// are never applied by a type the target is immune to.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct Resistances {
+    pub physical: f32, // Projectiles included
+    pub fire: f32,
+    pub ice: f32,
+    pub lightning: f32,
+    pub poison: f32,
+}
+
+impl Default for Resistances {
+    fn default() -> Self {
+        Self {
+            physical: 1.0,
+            fire: 1.0,
+            ice: 1.0,
+            lightning: 1.0,
+            poison: 1.0,
+        }
+    }
+}
+
+impl Resistances {
+    pub fn multiplier(&self, damage_type: DamageType) -> f32 {
+        match damage_type {
+            DamageType::Physical | DamageType::Projectile => self.physical,
+            DamageType::Fire => self.fire,
+            DamageType::Ice => self.ice,
+            DamageType::Lightning => self.lightning,
+            DamageType::Poison => self.poison,
+        }
+    }
+}
+
+// Every hit on an enemy or the player goes through this event; the sender has already
+// decided the hit lands (invulnerability, parries, hit registries), this applies it
+#[derive(Event, Clone, Copy)]
+pub struct DamageEvent {
+    pub target: Entity,
+    pub source: Option<Entity>,
+    pub amount: f32,
+    pub damage_type: DamageType,
+    pub crit: bool,
+    pub sweet_spot: bool, // Landed on the best part of the blade
+    pub position: Vec2,   // World-space hit point, where the damage number appears
+    pub direction: Vec2,  // Direction the blow travelled, zero if it has none
+    // Status ticks and chained hits: no statuses, combo or weapon effects of their own
+    pub indirect: bool,
+}
+
+impl DamageEvent {
+    // Damage the target actually takes
+    pub fn resisted_amount(&self, resistances: Option<&Resistances>) -> f32 {
+        self.amount * resistances.map_or(1.0, |r| r.multiplier(self.damage_type))
+    }
+}
+
+#[derive(Component)]
+struct DamageNumber {
+    timer: Timer,
+    velocity: Vec2,
+    color: Color,
+}
+
+// Added to an enemy the first time it takes damage
+#[derive(Component)]
+struct EnemyHealthBar {
+    bar: Entity,
+    fill: Entity,
+}
+
+fn apply_damage(
+    mut damage_events: EventReader<DamageEvent>,
+    mut enemy_query: Query<(&mut Enemy, Option<&Resistances>)>,
+    mut player_query: Query<&mut PlayerHealth>,
+    mut score: ResMut<Score>,
+) {
+    for event in damage_events.read() {
+        let _span = debug_span!(target: "enemy", "damage", target = ?event.target).entered();
+        if let Ok((mut enemy, resistances)) = enemy_query.get_mut(event.target) {
+            // Already dead, despawned by update_enemies
+            if enemy.health <= 0.0 {
+                continue;
+            }
+            let amount = event.resisted_amount(resistances);
+            enemy.health -= amount;
+            // Burns and chains don't keep a combo going on their own
+            if !event.indirect {
+                score.register_hit();
+            }
+            debug!(target: "enemy", amount, health = enemy.health, "enemy hit");
+            if enemy.health <= 0.0 {
+                info!(target: "enemy", source = ?event.source, "enemy destroyed");
+            }
+        } else if let Ok(mut health) = player_query.get_mut(event.target) {
+            health.take_damage(event.amount);
+            info!(target: "player", source = ?event.source, health = health.current, "player hit");
+        }
+    }
+}
+
+fn spawn_damage_numbers(
+    mut commands: Commands,
+    mut damage_events: EventReader<DamageEvent>,
+    resistance_query: Query<&Resistances>,
+) {
+    let mut rng = rand::thread_rng();
+    for event in damage_events.read() {
+        let amount = event.resisted_amount(resistance_query.get(event.target).ok());
+        let mut color = event.damage_type.color();
+        let font_size = if event.crit {
+            CRIT_NUMBER_SIZE
+        } else if event.sweet_spot {
+            SWEET_SPOT_NUMBER_SIZE
+        } else {
+            DAMAGE_NUMBER_SIZE
+        };
+        // Sweet-spot numbers take on a cyan edge, crits shout
+        if event.sweet_spot {
+            color = color.mix(&SWEET_SPOT_COLOR, 0.6);
+        }
+        let text = if event.crit {
+            format!("{:.0}!", amount)
+        } else {
+            format!("{:.0}", amount)
+        };
+        // Spread sideways a little so numbers from rapid hits don't stack
+        let velocity = Vec2::new(rng.gen_range(-20.0..20.0), DAMAGE_NUMBER_RISE);
+
+        commands.spawn((
+            Text2d::new(text),
+            TextFont {
+                font_size,
+                ..default()
+            },
+            TextColor(color),
+            Transform::from_translation(event.position.extend(10.0)),
+            DamageNumber {
+                timer: Timer::from_seconds(DAMAGE_NUMBER_LIFETIME, TimerMode::Once),
+                velocity,
+                color,
+            },
+        ));
+    }
+}
+
+fn animate_damage_numbers(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut number_query: Query<(Entity, &mut DamageNumber, &mut Transform, &mut TextColor)>,
+) {
+    for (entity, mut number, mut transform, mut text_color) in number_query.iter_mut() {
+        number.timer.tick(time.delta());
+        if number.timer.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        transform.translation += (number.velocity * time.delta_secs()).extend(0.0);
+        // Slow the drift so the number settles before fading out
+        number.velocity *= 1.0 - 3.0 * time.delta_secs();
+        text_color.0 = number.color.with_alpha(number.timer.fraction_remaining());
+    }
+}
+
+fn attach_health_bars(
+    mut commands: Commands,
+    mut damage_events: EventReader<DamageEvent>,
+    enemy_query: Query<&Enemy, Without<EnemyHealthBar>>,
+) {
+    // Several hits can land on the same enemy in one frame
+    let mut attached = Vec::new();
+
+    for event in damage_events.read() {
+        let Ok(enemy) = enemy_query.get(event.target) else {
+            continue;
+        };
+        if enemy.health <= 0.0 || attached.contains(&event.target) {
+            continue;
+        }
+
+        let bar = commands
+            .spawn((
+                Sprite::from_color(Color::srgba(0.1, 0.1, 0.1, 0.8), HEALTH_BAR_SIZE),
+                Transform::from_xyz(0.0, HEALTH_BAR_OFFSET, 0.1),
+                ChildOf(event.target),
+            ))
+            .id();
+        let fill = commands
+            .spawn((
+                Sprite {
+                    color: Color::srgb(0.9, 0.2, 0.2),
+                    custom_size: Some(HEALTH_BAR_SIZE),
+                    anchor: Anchor::CenterLeft,
+                    ..default()
+                },
+                // Anchored on its left edge so scaling shrinks it towards the left
+                Transform::from_xyz(-HEALTH_BAR_SIZE.x / 2.0, 0.0, 0.1),
+                ChildOf(bar),
+            ))
+            .id();
+        commands.entity(event.target).insert(EnemyHealthBar { bar, fill });
+        attached.push(event.target);
+    }
+}
+
+fn update_health_bars(
+    enemy_query: Query<(&Enemy, &Transform, &EnemyHealthBar)>,
+    mut bar_query: Query<&mut Transform, Without<Enemy>>,
+) {
+    for (enemy, enemy_transform, health_bar) in enemy_query.iter() {
+        // Enemies are dynamic bodies and get spun by collisions; keep the bar upright above them
+        let counter_rotation = enemy_transform.rotation.inverse();
+        if let Ok(mut bar_transform) = bar_query.get_mut(health_bar.bar) {
+            bar_transform.rotation = counter_rotation;
+            bar_transform.translation = counter_rotation * Vec3::new(0.0, HEALTH_BAR_OFFSET, 0.1);
+        }
+        if let Ok(mut fill_transform) = bar_query.get_mut(health_bar.fill) {
+            fill_transform.scale.x = (enemy.health / enemy.max_health).clamp(0.0, 1.0);
+        }
+    }
+}
@@ -0,0 +1,179 @@
+use crate::enemy::ENEMY_GROUP;
+use crate::input_map::{Action, ActionState};
+use crate::player::{move_player, PlayerMovement};
+use crate::swing_animation::{SwingAnimation, SwingInterrupt};
+use crate::sword::SwordNode;
+use crate::trail::spawn_mesh_afterimage;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+const DASH_SPEED: f32 = 900.0;
+const DASH_DURATION: f32 = 0.15;
+const DASH_COOLDOWN: f32 = 0.8;
+const DASH_INVULNERABILITY: f32 = 0.25; // Outlasts the dash itself slightly
+const AFTERIMAGE_INTERVAL: f32 = 0.03;
+const AFTERIMAGE_LIFETIME: f32 = 0.25;
+
+pub struct DashPlugin;
+
+impl Plugin for DashPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                (start_dash, apply_dash).chain().after(move_player),
+                update_invulnerability,
+            ),
+        );
+    }
+}
+
+#[derive(Component)]
+pub struct Dash {
+    pub speed: f32,
+    pub direction: Vec2,
+    pub active: Timer,   // Runs while the dash is moving the player
+    pub cooldown: Timer, // Runs from the start of the dash until the next one is allowed
+    afterimage_timer: Timer,
+}
+
+impl Default for Dash {
+    fn default() -> Self {
+        let mut active = Timer::from_seconds(DASH_DURATION, TimerMode::Once);
+        let mut cooldown = Timer::from_seconds(DASH_COOLDOWN, TimerMode::Once);
+        // Start out ready
+        active.tick(active.duration());
+        cooldown.tick(cooldown.duration());
+        Self {
+            speed: DASH_SPEED,
+            direction: Vec2::ZERO,
+            active,
+            cooldown,
+            afterimage_timer: Timer::from_seconds(AFTERIMAGE_INTERVAL, TimerMode::Repeating),
+        }
+    }
+}
+
+impl Dash {
+    pub fn is_dashing(&self) -> bool {
+        !self.active.finished()
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.cooldown.finished()
+    }
+}
+
+// Damage against an invulnerable entity is ignored
+#[derive(Component)]
+pub struct Invulnerable {
+    pub timer: Timer,
+}
+
+fn start_dash(
+    mut commands: Commands,
+    actions: Res<ActionState>,
+    mut player_query: Query<(Entity, &mut Dash, &Transform), With<PlayerMovement>>,
+    node_query: Query<&Transform, (With<SwordNode>, Without<PlayerMovement>)>,
+    mut swing_query: Query<&mut SwingAnimation>,
+) {
+    let Ok((player_entity, mut dash, transform)) = player_query.single_mut() else {
+        return;
+    };
+    if !actions.just_pressed(Action::Dash) || !dash.is_ready() {
+        return;
+    }
+
+    // A sword committed to its cut holds the dash back; windups and recoveries are broken off
+    if !swing_query.iter().all(|swing| swing.can_interrupt(SwingInterrupt::Dash)) {
+        return;
+    }
+    for mut swing in swing_query.iter_mut() {
+        swing.interrupt(SwingInterrupt::Dash);
+    }
+
+    // Dash where the player is steering, or towards the sword when standing still
+    let player_pos = transform.translation.truncate();
+    let aim = node_query
+        .iter()
+        .next()
+        .map(|node| node.translation.truncate() - player_pos)
+        .unwrap_or(Vec2::X);
+    dash.direction = actions.move_axis.try_normalize().unwrap_or(aim.normalize_or(Vec2::X));
+    dash.active.reset();
+    dash.cooldown.reset();
+
+    commands.entity(player_entity).insert(Invulnerable {
+        timer: Timer::from_seconds(DASH_INVULNERABILITY, TimerMode::Once),
+    });
+}
+
+// Override the walking movement while dashing; enemies are filtered out of the
+// character controller so the dash passes through them but still stops at walls, and out
+// of the player's solver groups so its collider doesn't shove them aside on the way
+fn apply_dash(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut player_query: Query<(
+        &mut Dash,
+        &mut PlayerMovement,
+        &mut KinematicCharacterController,
+        &mut SolverGroups,
+        &Transform,
+        &Mesh2d,
+    )>,
+) {
+    let passes_enemies = Group::ALL - ENEMY_GROUP;
+    for (mut dash, mut player_movement, mut controller, mut solver_groups, transform, mesh) in
+        player_query.iter_mut()
+    {
+        dash.cooldown.tick(time.delta());
+        if !dash.is_dashing() {
+            controller.filter_groups = None;
+            if solver_groups.filters != Group::ALL {
+                *solver_groups = SolverGroups::default();
+            }
+            continue;
+        }
+
+        dash.active.tick(time.delta());
+        let velocity = dash.direction * dash.speed;
+        controller.translation = Some(velocity * time.delta_secs());
+        controller.filter_groups = Some(CollisionGroups::new(Group::ALL, passes_enemies));
+        if solver_groups.filters != passes_enemies {
+            *solver_groups = SolverGroups::new(Group::ALL, passes_enemies);
+        }
+
+        if dash.active.finished() {
+            // Come out of the dash at walking speed rather than stopping dead
+            player_movement.velocity = dash.direction * player_movement.speed;
+        }
+
+        dash.afterimage_timer.tick(time.delta());
+        if dash.afterimage_timer.just_finished() {
+            spawn_mesh_afterimage(
+                &mut commands,
+                &mut materials,
+                mesh,
+                *transform,
+                Color::srgb(0.4, 0.6, 1.0),
+                0.5,
+                AFTERIMAGE_LIFETIME,
+            );
+        }
+    }
+}
+
+fn update_invulnerability(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut invulnerable_query: Query<(Entity, &mut Invulnerable)>,
+) {
+    for (entity, mut invulnerable) in invulnerable_query.iter_mut() {
+        invulnerable.timer.tick(time.delta());
+        if invulnerable.timer.finished() {
+            commands.entity(entity).remove::<Invulnerable>();
+        }
+    }
+}
@@ -0,0 +1,424 @@
+use crate::enemy::Enemy;
+use crate::input_map::{Action, ActionState};
+use crate::swing_animation::{SwingAnimation, SwingCurves, SwingMoment, SwingPhase, SwingType};
+use crate::swing_editor::SwingEditor;
+use crate::sword::{Sword, SwordNode};
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use std::collections::HashMap;
+use std::time::Duration;
+
+const PATH_SAMPLES: usize = 32;
+const MIN_PHASE_TIME: f32 = 0.01; // Phase progress divides by the phase's length
+
+pub struct DebugOverlayPlugin;
+
+impl Plugin for DebugOverlayPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(RapierDebugRenderPlugin {
+            enabled: false, // Collider outlines are part of the overlay
+            ..default()
+        })
+        .init_resource::<DebugOverlay>()
+        .init_resource::<SwingTuning>()
+        .add_systems(Startup, spawn_tuning_panel)
+        .add_systems(
+            Update,
+            (
+                toggle_overlay,
+                (handle_tuning_buttons, apply_swing_tuning, update_tuning_panel).chain(),
+                (draw_swing_paths, draw_locked_nodes, draw_enemy_velocities)
+                    .run_if(|overlay: Res<DebugOverlay>| overlay.enabled),
+            ),
+        );
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct DebugOverlay {
+    pub enabled: bool,
+}
+
+// Phase timings applied to every sword; edited from the tuning panel
+#[derive(Resource)]
+pub struct SwingTuning {
+    pub startup: f32,
+    pub swing: f32,
+    pub recovery: f32,
+    pub full_charge: f32,
+    pub attack_speed: f32, // Multiplies the player's attack speed stat, 1.0 = no override
+}
+
+impl Default for SwingTuning {
+    // Matches the timers swords are spawned with
+    fn default() -> Self {
+        Self {
+            startup: 0.1,
+            swing: 0.4,
+            recovery: 0.2,
+            full_charge: 0.8,
+            attack_speed: 1.0,
+        }
+    }
+}
+
+// A value the tuning panel can edit
+#[derive(Clone, Copy)]
+enum TuningField {
+    Startup,
+    Swing,
+    Recovery,
+    FullCharge,
+    AttackSpeed,
+    ControlPoint(usize, bool), // Index into the curve's control points, then true for y
+    EndRotation,
+}
+
+impl TuningField {
+    const ALL: [TuningField; 12] = [
+        TuningField::Startup,
+        TuningField::Swing,
+        TuningField::Recovery,
+        TuningField::FullCharge,
+        TuningField::AttackSpeed,
+        TuningField::ControlPoint(1, false),
+        TuningField::ControlPoint(1, true),
+        TuningField::ControlPoint(2, false),
+        TuningField::ControlPoint(2, true),
+        TuningField::ControlPoint(3, false),
+        TuningField::ControlPoint(3, true),
+        TuningField::EndRotation,
+    ];
+
+    fn label(&self) -> String {
+        match self {
+            TuningField::Startup => "Startup (s)".to_string(),
+            TuningField::Swing => "Swing (s)".to_string(),
+            TuningField::Recovery => "Recovery (s)".to_string(),
+            TuningField::FullCharge => "Full charge (s)".to_string(),
+            TuningField::AttackSpeed => "Attack speed override (x)".to_string(),
+            TuningField::ControlPoint(index, y) => {
+                format!("P{}.{}", index, if *y { "y" } else { "x" })
+            }
+            TuningField::EndRotation => "End rotation (turns)".to_string(),
+        }
+    }
+
+    // Amount one button press changes the value by
+    fn step(&self) -> f32 {
+        match self {
+            TuningField::ControlPoint(..) => 10.0,
+            TuningField::EndRotation => 0.05,
+            TuningField::AttackSpeed => 0.1,
+            _ => 0.02,
+        }
+    }
+
+    // Timings apply to every swing; the curve rows edit the swing type the swing editor has
+    // selected
+    fn get(&self, tuning: &SwingTuning, curves: &SwingCurves, swing_type: SwingType) -> f32 {
+        let curve = curves.get(swing_type);
+        match *self {
+            TuningField::Startup => tuning.startup,
+            TuningField::Swing => tuning.swing,
+            TuningField::Recovery => tuning.recovery,
+            TuningField::FullCharge => tuning.full_charge,
+            TuningField::AttackSpeed => tuning.attack_speed,
+            TuningField::ControlPoint(index, y) => {
+                let point = curve.path().points.get(index).copied().unwrap_or_default();
+                if y { point.y } else { point.x }
+            }
+            TuningField::EndRotation => curve.rotation_at(1.0) / std::f32::consts::TAU,
+        }
+    }
+
+    fn set(
+        &self,
+        tuning: &mut SwingTuning,
+        curves: &mut SwingCurves,
+        swing_type: SwingType,
+        value: f32,
+    ) {
+        let curve = curves.get_mut(swing_type);
+        let time = value.max(MIN_PHASE_TIME);
+        match *self {
+            TuningField::Startup => tuning.startup = time,
+            TuningField::Swing => tuning.swing = time,
+            TuningField::Recovery => tuning.recovery = time,
+            TuningField::FullCharge => tuning.full_charge = time,
+            TuningField::AttackSpeed => tuning.attack_speed = value.max(0.1),
+            TuningField::ControlPoint(index, y) => {
+                if let Some(mut point) = curve.path().points.get(index).copied() {
+                    if y {
+                        point.y = value;
+                    } else {
+                        point.x = value;
+                    }
+                    curve.set_point(index, point);
+                }
+            }
+            TuningField::EndRotation => {
+                // Moves the last rotation key; the swing editor edits the rest
+                if let Some(key) = curve.rotation_keys.last_mut() {
+                    key.angle = value * std::f32::consts::TAU;
+                }
+            }
+        }
+    }
+}
+
+#[derive(Component)]
+struct TuningPanel;
+
+#[derive(Component)]
+struct TuningTitle;
+
+#[derive(Component)]
+struct TuningValue(TuningField);
+
+#[derive(Component)]
+struct TuningButton {
+    field: TuningField,
+    direction: f32, // -1.0 or 1.0
+}
+
+fn toggle_overlay(
+    actions: Res<ActionState>,
+    mut overlay: ResMut<DebugOverlay>,
+    mut render_context: ResMut<DebugRenderContext>,
+    mut panel_query: Query<&mut Visibility, With<TuningPanel>>,
+) {
+    if !actions.just_pressed(Action::ToggleDebugOverlay) {
+        return;
+    }
+    overlay.enabled = !overlay.enabled;
+    render_context.enabled = overlay.enabled;
+    for mut visibility in panel_query.iter_mut() {
+        *visibility = if overlay.enabled {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
+
+fn spawn_tuning_panel(mut commands: Commands) {
+    let text_font = TextFont {
+        font_size: 12.0,
+        ..default()
+    };
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                right: Val::Px(20.0),
+                top: Val::Px(120.0),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(2.0),
+                padding: UiRect::all(Val::Px(8.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+            Visibility::Hidden,
+            TuningPanel,
+        ))
+        .with_children(|panel| {
+            panel.spawn((Text::new(""), text_font.clone(), TuningTitle));
+
+            for field in TuningField::ALL {
+                panel
+                    .spawn(Node {
+                        column_gap: Val::Px(6.0),
+                        align_items: AlignItems::Center,
+                        ..default()
+                    })
+                    .with_children(|row| {
+                        row.spawn((
+                            Text::new(field.label()),
+                            text_font.clone(),
+                            Node {
+                                width: Val::Px(130.0),
+                                ..default()
+                            },
+                        ));
+                        spawn_tuning_button(row, "-", field, -1.0, &text_font);
+                        row.spawn((
+                            Text::new(""),
+                            text_font.clone(),
+                            Node {
+                                width: Val::Px(50.0),
+                                ..default()
+                            },
+                            TuningValue(field),
+                        ));
+                        spawn_tuning_button(row, "+", field, 1.0, &text_font);
+                    });
+            }
+        });
+}
+
+fn spawn_tuning_button(
+    parent: &mut ChildSpawnerCommands,
+    label: &str,
+    field: TuningField,
+    direction: f32,
+    text_font: &TextFont,
+) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                padding: UiRect::axes(Val::Px(6.0), Val::Px(1.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.25, 0.25, 0.3)),
+            TuningButton { field, direction },
+        ))
+        .with_child((Text::new(label), text_font.clone()));
+}
+
+fn handle_tuning_buttons(
+    button_query: Query<(&Interaction, &TuningButton), Changed<Interaction>>,
+    editor: Res<SwingEditor>,
+    mut tuning: ResMut<SwingTuning>,
+    mut curves: ResMut<SwingCurves>,
+) {
+    for (interaction, button) in button_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let field = button.field;
+        let swing_type = editor.swing_type;
+        let value = field.get(&tuning, &curves, swing_type) + field.step() * button.direction;
+        field.set(&mut tuning, &mut curves, swing_type, value);
+    }
+}
+
+// Push the tuned timings onto every sword, and onto new swords as they are summoned
+fn apply_swing_tuning(
+    tuning: Res<SwingTuning>,
+    mut swing_query: Query<&mut SwingAnimation>,
+) {
+    for mut swing in swing_query.iter_mut() {
+        if !tuning.is_changed() && !swing.is_added() {
+            continue;
+        }
+        swing.startup_timer.set_duration(Duration::from_secs_f32(tuning.startup));
+        swing.swing_timer.set_duration(Duration::from_secs_f32(tuning.swing));
+        swing.end_timer.set_duration(Duration::from_secs_f32(tuning.recovery));
+        swing.full_charge_time = tuning.full_charge;
+    }
+}
+
+fn update_tuning_panel(
+    tuning: Res<SwingTuning>,
+    curves: Res<SwingCurves>,
+    editor: Res<SwingEditor>,
+    mut title_query: Query<&mut Text, With<TuningTitle>>,
+    mut value_query: Query<(&mut Text, &TuningValue), Without<TuningTitle>>,
+) {
+    if !tuning.is_changed() && !curves.is_changed() && !editor.is_changed() {
+        return;
+    }
+    let swing_type = editor.swing_type;
+    for mut text in title_query.iter_mut() {
+        text.0 = format!("Swing tuning, {:?} curve (F2 to close)", swing_type);
+    }
+    for (mut text, value) in value_query.iter_mut() {
+        text.0 = format!("{:.2}", value.0.get(&tuning, &curves, swing_type));
+    }
+}
+
+// Draw each attached sword's full swing: grey where the blade passes harmlessly and red over
+// its hit window, plus the curve's control points and the blade's current position
+fn draw_swing_paths(
+    mut gizmos: Gizmos,
+    curves: Res<SwingCurves>,
+    sword_query: Query<(&Sword, &SwingAnimation, &ChildOf, &GlobalTransform)>,
+    node_query: Query<&GlobalTransform, With<SwordNode>>,
+) {
+    for (sword, swing, child_of, sword_transform) in sword_query.iter() {
+        let Ok(node_transform) = node_query.get(child_of.parent()) else {
+            continue;
+        };
+        let to_world = |local: Vec2| node_transform.transform_point(local.extend(0.0)).truncate();
+        let curve = curves.get(swing.swing_type);
+        let (rest_pos, _) = sword.rest_pose();
+        // Idle swords preview their next swing faintly
+        let alpha = if swing.is_swinging { 1.0 } else { 0.3 };
+
+        let swing_point = |t: f32| to_world(swing.swing_pose(curve, t).position);
+        let end = swing_point(1.0);
+
+        // The window as it falls on the swing phase; a window reaching into the startup or
+        // recovery colours that whole leg
+        let (open, close) = curve.hit_window();
+        let progress_in_swing = |moment: SwingMoment| match moment.phase {
+            SwingPhase::Startup => 0.0,
+            SwingPhase::Swing => moment.progress.clamp(0.0, 1.0),
+            SwingPhase::Recovery => 1.0,
+        };
+        let (open_progress, close_progress) = (progress_in_swing(open), progress_in_swing(close));
+        let grey = Color::srgba(0.7, 0.7, 0.7, alpha);
+        let red = Color::srgba(1.0, 0.2, 0.2, alpha);
+        let leg_color = |cuts: bool| if cuts { red } else { grey };
+
+        let startup_cuts = open.phase == SwingPhase::Startup;
+        let recovery_cuts = close.phase == SwingPhase::Recovery;
+        gizmos.line_2d(to_world(rest_pos), swing_point(0.0), leg_color(startup_cuts));
+        gizmos.line_2d(end, to_world(rest_pos), leg_color(recovery_cuts));
+        for i in 0..PATH_SAMPLES {
+            let (from, to) = (i as f32 / PATH_SAMPLES as f32, (i + 1) as f32 / PATH_SAMPLES as f32);
+            let cuts = to > open_progress && from < close_progress;
+            gizmos.line_2d(swing_point(from), swing_point(to), leg_color(cuts));
+        }
+
+        // Control points and their hull
+        let control_points: Vec<Vec2> = (0..curve.path().points.len())
+            .map(|i| to_world(swing.curve_to_node(curve.path().handle(i))))
+            .collect();
+        let cyan = Color::srgba(0.2, 0.8, 1.0, alpha);
+        gizmos.linestrip_2d(control_points.iter().copied(), cyan.with_alpha(alpha * 0.4));
+        for point in control_points {
+            gizmos.circle_2d(point, 4.0, cyan);
+        }
+
+        if swing.is_swinging {
+            gizmos.circle_2d(sword_transform.translation().truncate(), 6.0, Color::WHITE);
+        }
+    }
+}
+
+fn draw_locked_nodes(mut gizmos: Gizmos, node_query: Query<&SwordNode>) {
+    for sword_node in node_query.iter() {
+        if let Some(position) = sword_node.locked_position {
+            let yellow = Color::srgb(1.0, 0.9, 0.2);
+            gizmos.cross_2d(position, 8.0, yellow);
+            gizmos.circle_2d(position, 12.0, yellow);
+        }
+    }
+}
+
+// Enemies move by writing their transform, so their velocity is measured frame to frame
+fn draw_enemy_velocities(
+    mut gizmos: Gizmos,
+    time: Res<Time>,
+    enemy_query: Query<(Entity, &Transform), With<Enemy>>,
+    mut last_positions: Local<HashMap<Entity, Vec2>>,
+) {
+    let delta = time.delta_secs();
+    let mut positions = HashMap::new();
+
+    for (entity, transform) in enemy_query.iter() {
+        let position = transform.translation.truncate();
+        if let Some(last) = last_positions.get(&entity).filter(|_| delta > 0.0) {
+            let velocity = (position - *last) / delta;
+            // Scaled so an arrow shows where the enemy will be in half a second
+            gizmos.arrow_2d(position, position + velocity * 0.5, Color::srgb(0.3, 1.0, 0.3));
+        }
+        positions.insert(entity, position);
+    }
+
+    *last_positions = positions;
+}
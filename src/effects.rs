@@ -0,0 +1,230 @@
+use crate::damage::DamageEvent;
+use crate::enemy::Enemy;
+use crate::particles::ParticleBurst;
+use bevy::prelude::*;
+use bevy::sprite::AlphaMode2d;
+
+const HIT_FLASH_DURATION: f32 = 0.12;
+const DEATH_DISSOLVE_DURATION: f32 = 0.3;
+
+pub struct EffectsPlugin;
+
+impl Plugin for EffectsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (hit_effects, update_hit_flash, start_dissolve, update_dissolve).chain(),
+        );
+    }
+}
+
+// Briefly washes an enemy's material out to white after a hit
+#[derive(Component)]
+pub struct HitFlash {
+    timer: Timer,
+    base_color: Color,
+}
+
+// A dead enemy shrinking and fading out before it is despawned
+#[derive(Component)]
+pub struct Dying {
+    timer: Timer,
+    start_scale: Vec3,
+    base_color: Option<Color>, // Set once the dissolve starts
+}
+
+impl Default for Dying {
+    fn default() -> Self {
+        Self {
+            timer: Timer::from_seconds(DEATH_DISSOLVE_DURATION, TimerMode::Once),
+            start_scale: Vec3::ONE,
+            base_color: None,
+        }
+    }
+}
+
+fn spark_burst() -> ParticleBurst {
+    ParticleBurst {
+        count: 10,
+        color: Color::srgb(1.0, 0.95, 0.6),
+        speed: 150.0..350.0,
+        lifetime: 0.1..0.25,
+        size: Vec2::new(8.0, 2.0),
+        drag: 6.0,
+        ..default()
+    }
+}
+
+// A wide golden starburst on top of the usual sparks
+fn crit_burst() -> ParticleBurst {
+    ParticleBurst {
+        count: 24,
+        color: Color::srgb(1.0, 0.8, 0.2),
+        speed: 250.0..500.0,
+        lifetime: 0.15..0.35,
+        size: Vec2::new(14.0, 3.0),
+        drag: 5.0,
+        ..default()
+    }
+}
+
+// A tight ring of bright motes where the tip connected
+fn sweet_spot_burst() -> ParticleBurst {
+    ParticleBurst {
+        count: 14,
+        color: Color::srgb(0.6, 1.0, 0.95),
+        speed: 180.0..200.0,
+        lifetime: 0.2..0.25,
+        size: Vec2::new(4.0, 4.0),
+        drag: 8.0,
+        ..default()
+    }
+}
+
+// Bits of the enemy thrown out the far side in the direction of the blow
+fn shard_spray(direction: Vec2, color: Color) -> ParticleBurst {
+    ParticleBurst {
+        count: 12,
+        color,
+        direction,
+        spread: 0.5,
+        speed: 120.0..300.0,
+        lifetime: 0.25..0.5,
+        size: Vec2::new(5.0, 3.0),
+        drag: 3.0,
+    }
+}
+
+fn hit_effects(
+    mut commands: Commands,
+    mut damage_events: EventReader<DamageEvent>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut enemy_query: Query<
+        (&MeshMaterial2d<ColorMaterial>, Option<&mut HitFlash>),
+        (With<Enemy>, Without<Dying>),
+    >,
+) {
+    // Enemies that started flashing this frame, with the color their material had
+    let mut flashed: Vec<(Entity, Color)> = Vec::new();
+
+    for event in damage_events.read() {
+        if !event.indirect {
+            spark_burst().emit(&mut commands, event.position);
+        }
+        if event.crit {
+            crit_burst().emit(&mut commands, event.position);
+        }
+        if event.sweet_spot {
+            sweet_spot_burst().emit(&mut commands, event.position);
+        }
+
+        let Ok((material, flash)) = enemy_query.get_mut(event.target) else {
+            continue;
+        };
+        let Some(material) = materials.get_mut(&material.0) else {
+            continue;
+        };
+
+        let base_color = flash
+            .as_ref()
+            .map(|f| f.base_color)
+            .or_else(|| flashed.iter().find(|(e, _)| *e == event.target).map(|(_, c)| *c))
+            .unwrap_or(material.color);
+        if event.direction != Vec2::ZERO {
+            shard_spray(event.direction, base_color).emit(&mut commands, event.position);
+        }
+
+        if let Some(mut flash) = flash {
+            flash.timer.reset();
+        } else if !flashed.iter().any(|(e, _)| *e == event.target) {
+            commands.entity(event.target).insert(HitFlash {
+                timer: Timer::from_seconds(HIT_FLASH_DURATION, TimerMode::Once),
+                base_color,
+            });
+            flashed.push((event.target, base_color));
+        }
+        material.color = Color::WHITE;
+    }
+}
+
+fn update_hit_flash(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut flash_query: Query<(Entity, &mut HitFlash, &MeshMaterial2d<ColorMaterial>)>,
+) {
+    for (entity, mut flash, material) in flash_query.iter_mut() {
+        flash.timer.tick(time.delta());
+        let Some(material) = materials.get_mut(&material.0) else {
+            continue;
+        };
+        material.color = Color::WHITE.mix(&flash.base_color, flash.timer.fraction());
+        if flash.timer.finished() {
+            commands.entity(entity).remove::<HitFlash>();
+        }
+    }
+}
+
+fn start_dissolve(
+    mut commands: Commands,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut dying_query: Query<
+        (
+            Entity,
+            &mut Dying,
+            &Transform,
+            &MeshMaterial2d<ColorMaterial>,
+            Option<&HitFlash>,
+        ),
+        Added<Dying>,
+    >,
+) {
+    for (entity, mut dying, transform, material, flash) in dying_query.iter_mut() {
+        let Some(material) = materials.get_mut(&material.0) else {
+            continue;
+        };
+        // Dissolve from the real color, not the middle of a hit flash
+        let base_color = flash.map(|f| f.base_color).unwrap_or(material.color);
+        material.color = base_color;
+        // Enemy materials start out opaque
+        material.alpha_mode = AlphaMode2d::Blend;
+        commands.entity(entity).remove::<HitFlash>();
+
+        dying.start_scale = transform.scale;
+        dying.base_color = Some(base_color);
+
+        ParticleBurst {
+            count: 20,
+            color: base_color,
+            speed: 80.0..220.0,
+            lifetime: 0.3..0.6,
+            size: Vec2::new(4.0, 4.0),
+            drag: 2.5,
+            ..default()
+        }
+        .emit(&mut commands, transform.translation.truncate());
+    }
+}
+
+fn update_dissolve(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut dying_query: Query<(Entity, &mut Dying, &mut Transform, &MeshMaterial2d<ColorMaterial>)>,
+) {
+    for (entity, mut dying, mut transform, material) in dying_query.iter_mut() {
+        dying.timer.tick(time.delta());
+        if dying.timer.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let remaining = dying.timer.fraction_remaining();
+        transform.scale = dying.start_scale * remaining;
+        if let (Some(material), Some(base_color)) =
+            (materials.get_mut(&material.0), dying.base_color)
+        {
+            material.color = base_color.with_alpha(remaining);
+        }
+    }
+}
@@ -2,7 +2,14 @@ use bevy::{prelude::*, window::PrimaryWindow};
 use bevy_rapier2d::prelude::*;
 use rand::Rng;
 
-use crate::player::PlayerMovement;
+use crate::arena::{ARENA_HEIGHT, ARENA_WIDTH};
+use crate::audio::EnemySounds;
+use crate::damage::{DamageEvent, DamageType, Resistances};
+use crate::dash::Invulnerable;
+use crate::effects::Dying;
+use crate::player::{PlayerHealth, PlayerMovement};
+use crate::projectile::{spawn_projectile, Projectile, ProjectileOwner};
+use crate::score::Score;
 
 #[derive(Component)]
 pub struct EnemySpawner {
@@ -13,20 +20,120 @@ pub struct EnemySpawner {
 #[derive(Component)]
 pub struct Enemy {
     pub health: f32,
-    pub speed: f32,
+    pub max_health: f32,
+    pub speed: f32, // Current speed, lowered by slows
+    pub base_speed: f32,
+    pub base_color: Color, // Material color when unaffected by any status
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum AttackKind {
+    Melee,  // Strikes the player when in range
+    Ranged, // Keeps its distance and fires projectiles
+}
+
+#[derive(Component)]
+pub struct EnemyAttack {
+    pub kind: AttackKind,
+    pub range: f32,
+    pub damage: f32,
+    pub cooldown: Timer,
+}
+
+// A staggered enemy can neither move nor attack until the timer runs out
+#[derive(Component)]
+pub struct Staggered {
+    pub timer: Timer,
+}
+
+const PROJECTILE_SPEED: f32 = 250.0;
+
+// Collision group enemies live in, so e.g. a dashing player can pass through them
+pub const ENEMY_GROUP: Group = Group::GROUP_2;
+
+// Knockback and shoves bleed off at this rate; an enemy given velocity v slides about
+// v / ENEMY_LINEAR_DAMPING units before stopping
+pub const ENEMY_LINEAR_DAMPING: f32 = 8.0;
+
+pub const SHOOTER_SOUNDS: EnemySounds = EnemySounds {
+    impact: "audio/impact_shell.ogg",
+    death: "audio/death_shooter.ogg",
+};
+pub const BRUTE_SOUNDS: EnemySounds = EnemySounds {
+    impact: "audio/impact_flesh.ogg",
+    death: "audio/death_brute.ogg",
+};
+
+// Enemies come in waves; the next wave starts a short while after the last enemy dies
+#[derive(Resource)]
+pub struct WaveState {
+    pub wave: u32,
+    pub to_spawn: u32, // Enemies of the current wave not spawned yet
+    pub alive: u32,
+    pub intermission: Timer,
+}
+
+impl Default for WaveState {
+    fn default() -> Self {
+        Self {
+            wave: 0,
+            to_spawn: 0,
+            alive: 0,
+            intermission: Timer::from_seconds(2.0, TimerMode::Once),
+        }
+    }
+}
+
+impl WaveState {
+    pub fn enemies_remaining(&self) -> u32 {
+        self.to_spawn + self.alive
+    }
+}
+
+fn enemies_in_wave(wave: u32) -> u32 {
+    3 + wave * 2
 }
 
 pub struct EnemyPlugin;
 
 impl Plugin for EnemyPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, (update_spawning, update_enemies));
+        app.init_resource::<WaveState>().add_systems(
+            Update,
+            (
+                update_waves,
+                update_spawning,
+                update_enemies,
+                update_enemy_attacks,
+                update_stagger,
+            ),
+        );
+    }
+}
+
+fn update_waves(
+    time: Res<Time>,
+    mut waves: ResMut<WaveState>,
+    enemy_query: Query<(), With<Enemy>>,
+) {
+    waves.alive = enemy_query.iter().count() as u32;
+    if waves.enemies_remaining() > 0 {
+        return;
+    }
+
+    waves.intermission.tick(time.delta());
+    if waves.intermission.finished() {
+        waves.wave += 1;
+        waves.to_spawn = enemies_in_wave(waves.wave);
+        waves.intermission.reset();
+        info!(target: "enemy", wave = waves.wave, enemies = waves.to_spawn, "wave started");
     }
 }
 
 pub fn update_spawning(
     primary_query: Query<&Window, With<PrimaryWindow>>,
     mut spawner_query: Query<&mut EnemySpawner>,
+    mut waves: ResMut<WaveState>,
     time: Res<Time>,
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
@@ -34,12 +141,18 @@ pub fn update_spawning(
 ) {
     for mut spawner in spawner_query.iter_mut() {
         spawner.timer -= time.delta_secs();
-        if spawner.timer <= 0. {
+        // Only spawn while the current wave still has enemies left to send
+        if spawner.timer <= 0. && waves.to_spawn > 0 {
             let Ok(primary) = primary_query.single() else {
                 return;
             };
 
             spawner.timer = spawner.cooldown;
+            waves.to_spawn -= 1;
+
+            // Spawn along the screen edge, but never outside the arena walls
+            let spawn_width = primary.width().min(ARENA_WIDTH - 80.0);
+            let spawn_height = primary.height().min(ARENA_HEIGHT - 80.0);
 
             let mut spawn_transform = Transform::default().with_scale(Vec3::splat(30.));
 
@@ -48,41 +161,97 @@ pub fn update_spawning(
             if rng.gen_range(0..2) == 1 {
                 if rng.gen_range(0..2) == 1 {
                     spawn_transform.translation = Vec3::new(
-                        primary.width() / 2.,
-                        rng.gen_range(-primary.height() / 2.0..primary.height() / 2.0),
+                        spawn_width / 2.,
+                        rng.gen_range(-spawn_height / 2.0..spawn_height / 2.0),
                         0.,
                     );
                 } else {
                     spawn_transform.translation = Vec3::new(
-                        -primary.width() / 2.,
-                        rng.gen_range(-primary.height() / 2.0..primary.height() / 2.0),
+                        -spawn_width / 2.,
+                        rng.gen_range(-spawn_height / 2.0..spawn_height / 2.0),
                         0.,
                     );
                 }
             } else if rng.gen_range(0..2) == 1 {
                 spawn_transform.translation = Vec3::new(
-                    rng.gen_range(-primary.width() / 2.0..primary.width() / 2.0),
-                    primary.height() / 2.,
+                    rng.gen_range(-spawn_width / 2.0..spawn_width / 2.0),
+                    spawn_height / 2.,
                     0.,
                 );
             } else {
                 spawn_transform.translation = Vec3::new(
-                    rng.gen_range(-primary.width() / 2.0..primary.width() / 2.0),
-                    -primary.height() / 2.,
+                    rng.gen_range(-spawn_width / 2.0..spawn_width / 2.0),
+                    -spawn_height / 2.,
                     0.,
                 );
             }
 
+            // One in three enemies is a ranged shooter
+            let (attack, color, sounds, resistances) = if rng.gen_range(0..3) == 0 {
+                (
+                    EnemyAttack {
+                        kind: AttackKind::Ranged,
+                        range: 300.0,
+                        damage: 10.0,
+                        cooldown: Timer::from_seconds(2.0, TimerMode::Repeating),
+                    },
+                    Color::srgb(0.6, 0.0, 0.6), // Purple
+                    SHOOTER_SOUNDS,
+                    // Insulated shell, but frail against anything that gets inside it
+                    Resistances {
+                        lightning: 0.5,
+                        fire: 1.25,
+                        poison: 1.5,
+                        ..default()
+                    },
+                )
+            } else {
+                (
+                    EnemyAttack {
+                        kind: AttackKind::Melee,
+                        range: 35.0,
+                        damage: 15.0,
+                        cooldown: Timer::from_seconds(1.0, TimerMode::Repeating),
+                    },
+                    Color::srgb(1.0, 0.0, 0.0), // Red color
+                    BRUTE_SOUNDS,
+                    // Thick hide shrugs off burns, the cold gets through
+                    Resistances {
+                        fire: 0.5,
+                        ice: 1.5,
+                        ..default()
+                    },
+                )
+            };
+
+            debug!(
+                target: "enemy",
+                kind = ?attack.kind,
+                position = ?spawn_transform.translation,
+                "enemy spawned"
+            );
             commands.spawn((
                 Enemy {
                     health: 100.0,
+                    max_health: 100.0,
                     speed: 50.0,
+                    base_speed: 50.0,
+                    base_color: color,
                 },
+                attack,
+                sounds,
+                resistances,
                 Mesh2d(meshes.add(Circle::default())),
-                MeshMaterial2d(materials.add(Color::srgb(1.0, 0.0, 0.0))), // Red color
+                MeshMaterial2d(materials.add(color)),
                 RigidBody::Dynamic,
                 Collider::ball(0.5),
+                CollisionGroups::new(ENEMY_GROUP, Group::ALL),
                 GravityScale(0.0),
+                Velocity::zero(),
+                Damping {
+                    linear_damping: ENEMY_LINEAR_DAMPING,
+                    ..default()
+                },
                 spawn_transform,
             ));
         }
@@ -91,19 +260,110 @@ pub fn update_spawning(
 
 pub fn update_enemies(
     time: Res<Time>,
-    mut enemy_query: Query<(&Enemy, &mut Transform, Entity), Without<PlayerMovement>>,
+    mut enemy_query: Query<
+        (&Enemy, &EnemyAttack, &mut Transform, Entity, Has<Staggered>),
+        (Without<PlayerMovement>, Without<Dying>),
+    >,
     player_query: Query<(&PlayerMovement, &Transform), Without<Enemy>>,
+    mut score: ResMut<Score>,
     mut commands: Commands,
 ) {
     if let Ok((_player_movement, player_transform)) = player_query.single() {
-        for (enemy, mut transform, entity) in enemy_query.iter_mut() {
-            let moving = Vec3::normalize(player_transform.translation - transform.translation)
-                * enemy.speed
-                * time.delta_secs();
-            transform.translation += moving;
+        for (enemy, attack, mut transform, entity, staggered) in enemy_query.iter_mut() {
+            // All enemy deaths end up here, whatever dealt the final blow
             if enemy.health <= 0. {
-                commands.entity(entity).despawn();
+                score.register_kill();
+                // Stop taking part in physics and let the death effect despawn it
+                commands
+                    .entity(entity)
+                    .remove::<(RigidBody, Collider)>()
+                    .insert(Dying::default());
+                continue;
+            }
+            if staggered {
+                continue;
+            }
+
+            // Ranged enemies hold position once the player is within reach
+            let to_player = player_transform.translation - transform.translation;
+            if attack.kind == AttackKind::Ranged && to_player.length() < attack.range * 0.8 {
+                continue;
+            }
+
+            let moving = Vec3::normalize(to_player) * enemy.speed * time.delta_secs();
+            transform.translation += moving;
+        }
+    }
+}
+
+pub fn update_enemy_attacks(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut enemy_query: Query<
+        (Entity, &mut EnemyAttack, &Transform),
+        (With<Enemy>, Without<Staggered>, Without<Dying>),
+    >,
+    player_query: Query<
+        (Entity, &Transform, Has<Invulnerable>),
+        (With<PlayerHealth>, Without<Enemy>),
+    >,
+    mut damage_events: EventWriter<DamageEvent>,
+) {
+    let Ok((player_entity, player_transform, invulnerable)) = player_query.single() else {
+        return;
+    };
+    let player_pos = player_transform.translation.truncate();
+
+    for (entity, mut attack, transform) in enemy_query.iter_mut() {
+        attack.cooldown.tick(time.delta());
+        let position = transform.translation.truncate();
+        if position.distance(player_pos) > attack.range || !attack.cooldown.just_finished() {
+            continue;
+        }
+
+        match attack.kind {
+            AttackKind::Melee => {
+                if invulnerable {
+                    continue;
+                }
+                damage_events.write(DamageEvent {
+                    target: player_entity,
+                    source: Some(entity),
+                    amount: attack.damage,
+                    damage_type: DamageType::Physical,
+                    crit: false,
+                    sweet_spot: false,
+                    position: player_pos,
+                    direction: (player_pos - position).normalize_or_zero(),
+                    indirect: false,
+                });
+            }
+            AttackKind::Ranged => {
+                let direction = (player_pos - position).normalize_or_zero();
+                spawn_projectile(
+                    &mut commands,
+                    &mut meshes,
+                    &mut materials,
+                    position + direction * 20.0,
+                    direction * PROJECTILE_SPEED,
+                    Projectile::new(ProjectileOwner::Enemy, Some(entity), attack.damage),
+                );
             }
         }
     }
 }
+
+fn update_stagger(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut stagger_query: Query<(Entity, &mut Staggered)>,
+) {
+    for (entity, mut staggered) in stagger_query.iter_mut() {
+        staggered.timer.tick(time.delta());
+        if staggered.timer.finished() {
+            commands.entity(entity).remove::<Staggered>();
+        }
+    }
+}
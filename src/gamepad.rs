@@ -0,0 +1,104 @@
+use bevy::input::gamepad::{GamepadConnection, GamepadConnectionEvent};
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+use bevy::window::CursorMoved;
+
+const STICK_DEADZONE: f32 = 0.2; // Radial deadzone applied to both sticks
+pub const AIM_RADIUS: f32 = 120.0; // Distance of the sword node from the player at full right-stick tilt
+
+pub struct GamepadPlugin;
+
+impl Plugin for GamepadPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GamepadState>().add_systems(
+            PreUpdate,
+            (handle_gamepad_connections, update_gamepad_state)
+                .chain()
+                .after(InputSystem),
+        );
+    }
+}
+
+// Stick state of the most recently used controller, filtered through the deadzone.
+// Buttons go through the input map like every other binding.
+#[derive(Resource, Default)]
+pub struct GamepadState {
+    pub active: Option<Entity>,
+    pub movement: Vec2,          // Left stick, length 0..1 for analog speed
+    pub aim: Vec2,               // Right stick, length 0..1
+    pub aim_offset: Vec2,        // Last right-stick aim relative to the player, kept when the stick is released
+    pub aiming_with_stick: bool, // Right stick drives the sword node until the mouse moves again
+}
+
+fn handle_gamepad_connections(
+    mut connection_events: EventReader<GamepadConnectionEvent>,
+    mut gamepad_state: ResMut<GamepadState>,
+    gamepad_query: Query<Entity, With<Gamepad>>,
+) {
+    for event in connection_events.read() {
+        match &event.connection {
+            GamepadConnection::Connected { name, .. } => {
+                info!(target: "input", gamepad = ?event.gamepad, "Gamepad connected: {}", name);
+                if gamepad_state.active.is_none() {
+                    gamepad_state.active = Some(event.gamepad);
+                }
+            }
+            GamepadConnection::Disconnected => {
+                info!(target: "input", gamepad = ?event.gamepad, "Gamepad disconnected");
+                if gamepad_state.active == Some(event.gamepad) {
+                    // Fall back to any other controller that is still plugged in
+                    gamepad_state.active = gamepad_query.iter().find(|g| *g != event.gamepad);
+                    gamepad_state.aiming_with_stick = false;
+                }
+            }
+        }
+    }
+}
+
+pub fn update_gamepad_state(
+    mut gamepad_state: ResMut<GamepadState>,
+    mut cursor_events: EventReader<CursorMoved>,
+    gamepad_query: Query<(Entity, &Gamepad)>,
+) {
+    // Whichever controller was touched last takes over
+    for (entity, gamepad) in gamepad_query.iter() {
+        let touched = gamepad.get_just_pressed().next().is_some()
+            || apply_deadzone(gamepad.left_stick()) != Vec2::ZERO
+            || apply_deadzone(gamepad.right_stick()) != Vec2::ZERO;
+        if touched {
+            gamepad_state.active = Some(entity);
+        }
+    }
+
+    let Some(gamepad) = gamepad_state
+        .active
+        .and_then(|entity| gamepad_query.get(entity).ok())
+        .map(|(_, gamepad)| gamepad)
+    else {
+        gamepad_state.movement = Vec2::ZERO;
+        gamepad_state.aim = Vec2::ZERO;
+        return;
+    };
+
+    gamepad_state.movement = apply_deadzone(gamepad.left_stick());
+    gamepad_state.aim = apply_deadzone(gamepad.right_stick());
+
+    if gamepad_state.aim != Vec2::ZERO {
+        gamepad_state.aiming_with_stick = true;
+        gamepad_state.aim_offset = gamepad_state.aim * AIM_RADIUS;
+    }
+    // Moving the mouse hands aiming back to the cursor
+    if cursor_events.read().count() > 0 {
+        gamepad_state.aiming_with_stick = false;
+    }
+}
+
+// Radial deadzone, rescaled so output still ramps smoothly from 0 at the edge of the deadzone
+fn apply_deadzone(stick: Vec2) -> Vec2 {
+    let length = stick.length();
+    if length < STICK_DEADZONE {
+        return Vec2::ZERO;
+    }
+    let scaled = ((length - STICK_DEADZONE) / (1.0 - STICK_DEADZONE)).min(1.0);
+    stick / length * scaled
+}
@@ -0,0 +1,339 @@
+use crate::dash::Dash;
+use crate::enemy::WaveState;
+use crate::parry::SwordStance;
+use crate::player::{PlayerHealth, PlayerMovement};
+use crate::score::Score;
+use bevy::prelude::*;
+
+const HUD_FONT_SIZE: f32 = 18.0;
+const ICON_SIZE: f32 = 40.0;
+
+pub struct HudPlugin;
+
+impl Plugin for HudPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_hud).add_systems(
+            Update,
+            (
+                update_health_bar,
+                update_score_text,
+                update_wave_text,
+                update_combo,
+                update_cooldown_icons,
+            ),
+        );
+
+        #[cfg(debug_assertions)]
+        app.add_systems(Startup, debug::spawn_swing_readout)
+            .add_systems(
+                Update,
+                (debug::toggle_swing_readout, debug::update_swing_readout),
+            );
+    }
+}
+
+#[derive(Component)]
+struct HealthBarFill;
+
+#[derive(Component)]
+struct HealthText;
+
+#[derive(Component)]
+struct ScoreText;
+
+#[derive(Component)]
+struct WaveText;
+
+#[derive(Component)]
+struct ComboText;
+
+#[derive(Component)]
+struct ComboBarFill;
+
+#[derive(Clone, Copy)]
+enum Ability {
+    Dash,
+    Parry,
+}
+
+// Overlay that fills up as the ability recharges
+#[derive(Component)]
+struct CooldownFill(Ability);
+
+fn spawn_hud(mut commands: Commands) {
+    let text_font = TextFont {
+        font_size: HUD_FONT_SIZE,
+        ..default()
+    };
+
+    // Top left: health
+    commands
+        .spawn(Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(20.0),
+            top: Val::Px(20.0),
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(4.0),
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((Text::new(""), text_font.clone(), HealthText));
+            parent
+                .spawn((
+                    Node {
+                        width: Val::Px(200.0),
+                        height: Val::Px(12.0),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.8)),
+                ))
+                .with_child((
+                    Node {
+                        width: Val::Percent(100.0),
+                        height: Val::Percent(100.0),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.8, 0.1, 0.1)),
+                    HealthBarFill,
+                ));
+        });
+
+    // Top right: score, wave and combo
+    commands
+        .spawn(Node {
+            position_type: PositionType::Absolute,
+            right: Val::Px(20.0),
+            top: Val::Px(20.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::FlexEnd,
+            row_gap: Val::Px(4.0),
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((Text::new(""), text_font.clone(), ScoreText));
+            parent.spawn((Text::new(""), text_font.clone(), WaveText));
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: HUD_FONT_SIZE * 1.5,
+                    ..default()
+                },
+                TextColor(Color::srgb(1.0, 0.8, 0.2)),
+                ComboText,
+            ));
+            parent
+                .spawn(Node {
+                    width: Val::Px(120.0),
+                    height: Val::Px(4.0),
+                    ..default()
+                })
+                .with_child((
+                    Node {
+                        width: Val::Percent(0.0),
+                        height: Val::Percent(100.0),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(1.0, 0.8, 0.2)),
+                    ComboBarFill,
+                ));
+        });
+
+    // Bottom left: ability cooldowns
+    commands
+        .spawn(Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(20.0),
+            bottom: Val::Px(20.0),
+            column_gap: Val::Px(8.0),
+            ..default()
+        })
+        .with_children(|parent| {
+            for (ability, label) in [(Ability::Dash, "Dash"), (Ability::Parry, "Parry")] {
+                parent
+                    .spawn((
+                        Node {
+                            width: Val::Px(ICON_SIZE),
+                            height: Val::Px(ICON_SIZE),
+                            align_items: AlignItems::FlexEnd,
+                            justify_content: JustifyContent::Center,
+                            ..default()
+                        },
+                        BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.8)),
+                    ))
+                    .with_children(|icon| {
+                        icon.spawn((
+                            Node {
+                                position_type: PositionType::Absolute,
+                                bottom: Val::Px(0.0),
+                                width: Val::Percent(100.0),
+                                height: Val::Percent(100.0),
+                                ..default()
+                            },
+                            BackgroundColor(Color::srgba(0.4, 0.6, 1.0, 0.8)),
+                            CooldownFill(ability),
+                        ));
+                        icon.spawn((
+                            Text::new(label),
+                            TextFont {
+                                font_size: 11.0,
+                                ..default()
+                            },
+                        ));
+                    });
+            }
+        });
+}
+
+fn update_health_bar(
+    player_query: Query<&PlayerHealth, With<PlayerMovement>>,
+    mut fill_query: Query<&mut Node, With<HealthBarFill>>,
+    mut text_query: Query<&mut Text, With<HealthText>>,
+) {
+    let Ok(health) = player_query.single() else {
+        return;
+    };
+    for mut node in fill_query.iter_mut() {
+        node.width = Val::Percent(health.current / health.max * 100.0);
+    }
+    for mut text in text_query.iter_mut() {
+        text.0 = format!("HP {:.0}/{:.0}", health.current, health.max);
+    }
+}
+
+fn update_score_text(score: Res<Score>, mut text_query: Query<&mut Text, With<ScoreText>>) {
+    if !score.is_changed() {
+        return;
+    }
+    for mut text in text_query.iter_mut() {
+        text.0 = format!("Score {}", score.points);
+    }
+}
+
+fn update_wave_text(waves: Res<WaveState>, mut text_query: Query<&mut Text, With<WaveText>>) {
+    for mut text in text_query.iter_mut() {
+        text.0 = if waves.wave == 0 {
+            "Get ready...".to_string()
+        } else {
+            format!("Wave {} - {} left", waves.wave, waves.enemies_remaining())
+        };
+    }
+}
+
+fn update_combo(
+    score: Res<Score>,
+    mut text_query: Query<&mut Text, With<ComboText>>,
+    mut fill_query: Query<&mut Node, With<ComboBarFill>>,
+) {
+    // Only show a combo once it is worth mentioning
+    let showing = score.combo >= 2;
+    for mut text in text_query.iter_mut() {
+        text.0 = if showing {
+            format!("{} hit combo", score.combo)
+        } else {
+            String::new()
+        };
+    }
+    for mut node in fill_query.iter_mut() {
+        let remaining = if showing {
+            score.combo_timer.fraction_remaining()
+        } else {
+            0.0
+        };
+        node.width = Val::Percent(remaining * 100.0);
+    }
+}
+
+fn update_cooldown_icons(
+    dash_query: Query<&Dash, With<PlayerMovement>>,
+    stance_query: Query<&SwordStance>,
+    mut fill_query: Query<(&mut Node, &CooldownFill)>,
+) {
+    for (mut node, fill) in fill_query.iter_mut() {
+        let ready = match fill.0 {
+            Ability::Dash => dash_query
+                .single()
+                .map(|dash| dash.cooldown.fraction())
+                .unwrap_or(1.0),
+            // The parry is as ready as the slowest sword
+            Ability::Parry => stance_query
+                .iter()
+                .map(|stance| match stance {
+                    SwordStance::Ready => 1.0,
+                    SwordStance::Parrying { .. } => 0.0,
+                    SwordStance::Recovering { cooldown } => cooldown.fraction(),
+                })
+                .fold(1.0, f32::min),
+        };
+        node.height = Val::Percent(ready * 100.0);
+    }
+}
+
+// Per-sword swing state, only compiled into dev builds
+#[cfg(debug_assertions)]
+mod debug {
+    use crate::input_map::{Action, ActionState};
+    use crate::swing_animation::SwingAnimation;
+    use crate::sword::Sword;
+    use bevy::prelude::*;
+
+    #[derive(Component)]
+    pub struct SwingReadout;
+
+    pub fn spawn_swing_readout(mut commands: Commands) {
+        commands.spawn((
+            Text::new(""),
+            TextFont {
+                font_size: 12.0,
+                ..default()
+            },
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(20.0),
+                top: Val::Px(80.0),
+                ..default()
+            },
+            Visibility::Hidden,
+            SwingReadout,
+        ));
+    }
+
+    pub fn toggle_swing_readout(
+        actions: Res<ActionState>,
+        mut readout_query: Query<&mut Visibility, With<SwingReadout>>,
+    ) {
+        if !actions.just_pressed(Action::ToggleSwingDebug) {
+            return;
+        }
+        for mut visibility in readout_query.iter_mut() {
+            visibility.toggle_visible_hidden();
+        }
+    }
+
+    pub fn update_swing_readout(
+        sword_query: Query<(Entity, &Sword, &SwingAnimation)>,
+        mut readout_query: Query<(&mut Text, &Visibility), With<SwingReadout>>,
+    ) {
+        for (mut text, visibility) in readout_query.iter_mut() {
+            if *visibility == Visibility::Hidden {
+                continue;
+            }
+            let mut swords: Vec<_> = sword_query.iter().collect();
+            swords.sort_by_key(|(_, sword, _)| sword.slot);
+            text.0 = swords
+                .iter()
+                .map(|(entity, sword, swing)| {
+                    let phase = if swing.is_swinging {
+                        format!("{:?}", swing.current_phase)
+                    } else {
+                        "Idle".to_string()
+                    };
+                    format!(
+                        "sword {} {:?}: {} charge {:.2}",
+                        sword.slot, entity, phase, swing.charge
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+        }
+    }
+}
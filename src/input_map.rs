@@ -0,0 +1,388 @@
+use crate::gamepad::{update_gamepad_state, GamepadState};
+use bevy::ecs::system::SystemParam;
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+use bevy::reflect::{DynamicEnum, DynamicVariant, Enum, FromReflect};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+pub const BINDINGS_PATH: &str = "config/input_bindings.ron";
+
+pub struct InputMapPlugin;
+
+impl Plugin for InputMapPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(InputMap::load_or_default(BINDINGS_PATH))
+            .init_resource::<ActionState>()
+            .init_resource::<Rebinding>()
+            .add_systems(
+                PreUpdate,
+                (capture_rebind, update_action_state)
+                    .chain()
+                    .after(InputSystem)
+                    .after(update_gamepad_state),
+            );
+    }
+}
+
+// Everything the player can do; gameplay systems query these instead of raw keys
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+pub enum Action {
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    Swing,
+    HeavySwing,
+    Parry,
+    Throw,
+    Dash,
+    SummonSword,
+    CycleFormation,
+    ToggleSwingMode,
+    CycleWeapon,
+    ToggleCursor,
+    Pause,
+    ToggleBindingsMenu,
+    ToggleSwingDebug,
+    ToggleDebugOverlay,
+    ToggleSwingEditor,
+}
+
+impl Action {
+    pub const ALL: [Action; 19] = [
+        Action::MoveUp,
+        Action::MoveDown,
+        Action::MoveLeft,
+        Action::MoveRight,
+        Action::Swing,
+        Action::HeavySwing,
+        Action::Parry,
+        Action::Throw,
+        Action::Dash,
+        Action::SummonSword,
+        Action::CycleFormation,
+        Action::ToggleSwingMode,
+        Action::CycleWeapon,
+        Action::ToggleCursor,
+        Action::Pause,
+        Action::ToggleBindingsMenu,
+        Action::ToggleSwingDebug,
+        Action::ToggleDebugOverlay,
+        Action::ToggleSwingEditor,
+    ];
+
+    // Menu actions keep working while gameplay input is blocked (paused or rebinding)
+    pub fn is_menu_action(&self) -> bool {
+        matches!(
+            self,
+            Action::Pause
+                | Action::ToggleBindingsMenu
+                | Action::ToggleSwingDebug
+                | Action::ToggleDebugOverlay
+                | Action::ToggleSwingEditor
+        )
+    }
+}
+
+// A single physical input. Stored in the config file as "Device/Variant", e.g. "Key/KeyW"
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum Binding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    Gamepad(GamepadButton),
+}
+
+impl fmt::Display for Binding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Binding::Key(key) => write!(f, "Key/{}", key.variant_name()),
+            Binding::Mouse(button) => write!(f, "Mouse/{}", button.variant_name()),
+            Binding::Gamepad(button) => write!(f, "Gamepad/{}", button.variant_name()),
+        }
+    }
+}
+
+impl FromStr for Binding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (device, name) = s
+            .split_once('/')
+            .ok_or_else(|| format!("binding `{s}` has no device prefix"))?;
+        let binding = match device {
+            "Key" => parse_unit_variant(name).map(Binding::Key),
+            "Mouse" => parse_unit_variant(name).map(Binding::Mouse),
+            "Gamepad" => parse_unit_variant(name).map(Binding::Gamepad),
+            _ => None,
+        };
+        binding.ok_or_else(|| format!("unknown binding `{s}`"))
+    }
+}
+
+impl From<Binding> for String {
+    fn from(binding: Binding) -> Self {
+        binding.to_string()
+    }
+}
+
+impl TryFrom<String> for Binding {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+// Build a fieldless enum variant (KeyW, Left, South...) from its name through reflection
+fn parse_unit_variant<T: FromReflect>(name: &str) -> Option<T> {
+    T::from_reflect(&DynamicEnum::new(name, DynamicVariant::Unit))
+}
+
+// Raw device state, bundled so bindings can be checked in one call
+#[derive(SystemParam)]
+pub struct RawInputs<'w, 's> {
+    keys: Res<'w, ButtonInput<KeyCode>>,
+    mouse: Res<'w, ButtonInput<MouseButton>>,
+    gamepads: Query<'w, 's, &'static Gamepad>,
+}
+
+impl RawInputs<'_, '_> {
+    pub fn pressed(&self, binding: Binding) -> bool {
+        match binding {
+            Binding::Key(key) => self.keys.pressed(key),
+            Binding::Mouse(button) => self.mouse.pressed(button),
+            Binding::Gamepad(button) => self.gamepads.iter().any(|g| g.pressed(button)),
+        }
+    }
+
+    pub fn just_pressed(&self, binding: Binding) -> bool {
+        match binding {
+            Binding::Key(key) => self.keys.just_pressed(key),
+            Binding::Mouse(button) => self.mouse.just_pressed(button),
+            Binding::Gamepad(button) => self.gamepads.iter().any(|g| g.just_pressed(button)),
+        }
+    }
+
+    // First input pressed this frame on any device, used when capturing a new binding
+    pub fn first_just_pressed(&self) -> Option<Binding> {
+        self.keys
+            .get_just_pressed()
+            .next()
+            .map(|key| Binding::Key(*key))
+            .or_else(|| self.mouse.get_just_pressed().next().map(|b| Binding::Mouse(*b)))
+            .or_else(|| {
+                self.gamepads
+                    .iter()
+                    .find_map(|g| g.get_just_pressed().next().map(|b| Binding::Gamepad(*b)))
+            })
+    }
+}
+
+// Action -> bindings table, persisted to BINDINGS_PATH
+#[derive(Resource, Clone, Serialize, Deserialize)]
+pub struct InputMap {
+    bindings: BTreeMap<Action, Vec<Binding>>,
+}
+
+impl Default for InputMap {
+    fn default() -> Self {
+        use Binding::{Gamepad as Pad, Key, Mouse};
+        let bindings = BTreeMap::from([
+            (Action::MoveUp, vec![Key(KeyCode::KeyW), Key(KeyCode::ArrowUp), Pad(GamepadButton::DPadUp)]),
+            (Action::MoveDown, vec![Key(KeyCode::KeyS), Key(KeyCode::ArrowDown), Pad(GamepadButton::DPadDown)]),
+            (Action::MoveLeft, vec![Key(KeyCode::KeyA), Key(KeyCode::ArrowLeft), Pad(GamepadButton::DPadLeft)]),
+            (Action::MoveRight, vec![Key(KeyCode::KeyD), Key(KeyCode::ArrowRight), Pad(GamepadButton::DPadRight)]),
+            (Action::Swing, vec![Mouse(MouseButton::Left), Pad(GamepadButton::RightTrigger2), Pad(GamepadButton::West)]),
+            (Action::HeavySwing, vec![Mouse(MouseButton::Middle), Pad(GamepadButton::North)]),
+            (Action::Parry, vec![Mouse(MouseButton::Right), Pad(GamepadButton::LeftTrigger2), Pad(GamepadButton::East)]),
+            (Action::Throw, vec![Key(KeyCode::KeyQ), Pad(GamepadButton::RightTrigger)]),
+            (Action::Dash, vec![Key(KeyCode::ShiftLeft), Key(KeyCode::Space), Pad(GamepadButton::South)]),
+            (Action::SummonSword, vec![Key(KeyCode::KeyE)]),
+            (Action::CycleFormation, vec![Key(KeyCode::KeyF), Pad(GamepadButton::LeftTrigger)]),
+            (Action::ToggleSwingMode, vec![Key(KeyCode::KeyG)]),
+            (Action::CycleWeapon, vec![Key(KeyCode::KeyR), Pad(GamepadButton::RightThumb)]),
+            (Action::ToggleCursor, vec![Key(KeyCode::Tab)]),
+            (Action::Pause, vec![Key(KeyCode::Escape), Pad(GamepadButton::Start)]),
+            (Action::ToggleBindingsMenu, vec![Key(KeyCode::F1), Pad(GamepadButton::Select)]),
+            (Action::ToggleSwingDebug, vec![Key(KeyCode::F3)]),
+            (Action::ToggleDebugOverlay, vec![Key(KeyCode::F2)]),
+            (Action::ToggleSwingEditor, vec![Key(KeyCode::F4)]),
+        ]);
+        Self { bindings }
+    }
+}
+
+impl InputMap {
+    pub fn bindings(&self, action: Action) -> &[Binding] {
+        self.bindings.get(&action).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    pub fn add_binding(&mut self, action: Action, binding: Binding) {
+        let bindings = self.bindings.entry(action).or_default();
+        if !bindings.contains(&binding) {
+            bindings.push(binding);
+        }
+    }
+
+    pub fn clear_bindings(&mut self, action: Action) {
+        self.bindings.insert(action, Vec::new());
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let text = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        let loaded: InputMap = ron::from_str(&text).map_err(|e| e.to_string())?;
+
+        // Actions added since the file was written keep their default bindings
+        let mut input_map = InputMap::default();
+        input_map.bindings.extend(loaded.bindings);
+        Ok(input_map)
+    }
+
+    pub fn load_or_default(path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref();
+        if !path.exists() {
+            return InputMap::default();
+        }
+        InputMap::load(path).unwrap_or_else(|error| {
+            let path = path.display();
+            warn!(target: "input", "Failed to load input bindings from {}: {}", path, error);
+            InputMap::default()
+        })
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), String> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let text = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|e| e.to_string())?;
+        fs::write(path, text).map_err(|e| e.to_string())
+    }
+}
+
+// Per-frame action state derived from the input map
+#[derive(Resource, Default)]
+pub struct ActionState {
+    pressed: HashSet<Action>,
+    just_pressed: HashSet<Action>,
+    pub move_axis: Vec2, // Digital directions plus the left stick
+}
+
+impl ActionState {
+    pub fn pressed(&self, action: Action) -> bool {
+        self.pressed.contains(&action)
+    }
+
+    pub fn just_pressed(&self, action: Action) -> bool {
+        self.just_pressed.contains(&action)
+    }
+}
+
+// Rebinding in progress: the next input pressed is added to `action`
+#[derive(Resource, Default)]
+pub struct Rebinding {
+    pub action: Option<Action>,
+    pub menu_open: bool,
+    armed: bool, // Skips the frame the rebind was requested, so the click that started it isn't captured
+}
+
+impl Rebinding {
+    pub fn start(&mut self, action: Action) {
+        self.action = Some(action);
+        self.armed = false;
+    }
+
+    pub fn cancel(&mut self) {
+        self.action = None;
+    }
+
+    // Gameplay actions are ignored while the bindings menu is up
+    pub fn blocks_gameplay(&self) -> bool {
+        self.menu_open || self.action.is_some()
+    }
+}
+
+fn capture_rebind(
+    inputs: RawInputs,
+    mut rebinding: ResMut<Rebinding>,
+    mut input_map: ResMut<InputMap>,
+) {
+    let Some(action) = rebinding.action else {
+        return;
+    };
+    if !rebinding.armed {
+        rebinding.armed = true;
+        return;
+    }
+    let Some(binding) = inputs.first_just_pressed() else {
+        return;
+    };
+
+    rebinding.cancel();
+    if binding == Binding::Key(KeyCode::Escape) {
+        return;
+    }
+
+    input_map.add_binding(action, binding);
+    if let Err(error) = input_map.save(BINDINGS_PATH) {
+        warn!(target: "input", "Failed to save input bindings: {}", error);
+    }
+}
+
+pub fn update_action_state(
+    inputs: RawInputs,
+    input_map: Res<InputMap>,
+    rebinding: Res<Rebinding>,
+    gamepad: Res<GamepadState>,
+    time: Res<Time<Virtual>>,
+    mut action_state: ResMut<ActionState>,
+) {
+    action_state.pressed.clear();
+    action_state.just_pressed.clear();
+    action_state.move_axis = Vec2::ZERO;
+
+    // While rebinding, every press belongs to the capture
+    if rebinding.action.is_some() {
+        return;
+    }
+    let gameplay_blocked = rebinding.blocks_gameplay() || time.is_paused();
+
+    for action in Action::ALL {
+        if gameplay_blocked && !action.is_menu_action() {
+            continue;
+        }
+        let bindings = input_map.bindings(action);
+        if bindings.iter().any(|b| inputs.pressed(*b)) {
+            action_state.pressed.insert(action);
+        }
+        if bindings.iter().any(|b| inputs.just_pressed(*b)) {
+            action_state.just_pressed.insert(action);
+        }
+    }
+
+    if gameplay_blocked {
+        return;
+    }
+
+    let mut move_axis = gamepad.movement;
+    if action_state.pressed(Action::MoveUp) {
+        move_axis.y += 1.0;
+    }
+    if action_state.pressed(Action::MoveDown) {
+        move_axis.y -= 1.0;
+    }
+    if action_state.pressed(Action::MoveLeft) {
+        move_axis.x -= 1.0;
+    }
+    if action_state.pressed(Action::MoveRight) {
+        move_axis.x += 1.0;
+    }
+    action_state.move_axis = move_axis;
+}
@@ -0,0 +1,299 @@
+use crate::damage::{DamageEvent, Resistances};
+use crate::effects::Dying;
+use crate::enemy::{Enemy, EnemyAttack};
+use crate::swing_animation::{
+    SwingFinishedEvent, SwingNotifyEvent, SwingPhaseChangedEvent, SwingStartedEvent,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+pub const LOGGING_CONFIG_PATH: &str = "config/logging.ron";
+
+// Log targets are per subsystem (sword, swing, collision, enemy, player, input, audio),
+// so e.g. "collision=trace" turns on every contact without flooding the rest
+const DEFAULT_FILTER: &str = "wgpu=error,naga=warn,collision=warn";
+
+// Read before the app is built, since the log filter has to be known up front.
+// RUST_LOG, when set, takes precedence over `filter`.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LoggingConfig {
+    pub filter: String,
+    pub event_log: Option<PathBuf>, // JSON-lines gameplay event log, disabled when None
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            filter: DEFAULT_FILTER.to_string(),
+            event_log: None,
+        }
+    }
+}
+
+impl LoggingConfig {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let text = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        ron::from_str(&text).map_err(|e| e.to_string())
+    }
+
+    pub fn load_or_default(path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref();
+        if !path.exists() {
+            return LoggingConfig::default();
+        }
+        LoggingConfig::load(path).unwrap_or_else(|error| {
+            // The log subscriber doesn't exist yet
+            eprintln!("Failed to load logging config from {}: {}", path.display(), error);
+            LoggingConfig::default()
+        })
+    }
+}
+
+// Writes gameplay events to the configured event log, if any
+pub struct LoggingPlugin {
+    pub event_log: Option<PathBuf>,
+}
+
+impl Plugin for LoggingPlugin {
+    fn build(&self, app: &mut App) {
+        let Some(path) = &self.event_log else {
+            return;
+        };
+        match EventLog::create(path) {
+            Ok(event_log) => {
+                info!("Writing gameplay event log to {}", path.display());
+                app.insert_resource(event_log).add_systems(
+                    Update,
+                    (
+                        log_spawns,
+                        log_swings,
+                        log_swing_phases,
+                        log_swing_notifies,
+                        log_swing_finishes,
+                        log_hits,
+                        log_deaths,
+                        flush_event_log,
+                    )
+                        .chain(),
+                );
+            }
+            Err(error) => warn!("Failed to create event log {}: {}", path.display(), error),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+enum GameplayEvent {
+    Spawn {
+        enemy: u64,
+        kind: String,
+        x: f32,
+        y: f32,
+    },
+    SwingStarted {
+        sword: u64,
+        swing_type: String,
+        charge: f32,
+        speed: f32,
+    },
+    SwingPhase {
+        sword: u64,
+        phase: String,
+    },
+    SwingNotify {
+        sword: u64,
+        swing_type: String,
+        name: String,
+    },
+    SwingFinished {
+        sword: u64,
+        interrupted: bool,
+    },
+    Hit {
+        target: u64,
+        source: Option<u64>,
+        amount: f32,     // Damage taken, after the target's resistances
+        raw_amount: f32, // Damage dealt, before them
+        damage_type: String,
+        crit: bool,
+        sweet_spot: bool,
+        x: f32,
+        y: f32,
+    },
+    Death {
+        enemy: u64,
+        x: f32,
+        y: f32,
+    },
+}
+
+#[derive(Serialize)]
+struct LogLine {
+    time: f32, // Seconds of game time
+    #[serde(flatten)]
+    event: GameplayEvent,
+}
+
+#[derive(Resource)]
+struct EventLog {
+    writer: BufWriter<File>,
+}
+
+impl EventLog {
+    fn create(path: &Path) -> std::io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        Ok(Self {
+            writer: BufWriter::new(File::create(path)?),
+        })
+    }
+
+    fn write(&mut self, time: f32, event: GameplayEvent) {
+        let line = LogLine { time, event };
+        let result = serde_json::to_writer(&mut self.writer, &line)
+            .map_err(std::io::Error::from)
+            .and_then(|_| self.writer.write_all(b"\n"));
+        if let Err(error) = result {
+            warn!("Failed to write event log: {}", error);
+        }
+    }
+}
+
+fn log_spawns(
+    time: Res<Time>,
+    mut event_log: ResMut<EventLog>,
+    enemy_query: Query<(Entity, &EnemyAttack, &Transform), Added<Enemy>>,
+) {
+    for (entity, attack, transform) in enemy_query.iter() {
+        event_log.write(
+            time.elapsed_secs(),
+            GameplayEvent::Spawn {
+                enemy: entity.to_bits(),
+                kind: format!("{:?}", attack.kind),
+                x: transform.translation.x,
+                y: transform.translation.y,
+            },
+        );
+    }
+}
+
+fn log_swings(
+    time: Res<Time>,
+    mut event_log: ResMut<EventLog>,
+    mut swing_events: EventReader<SwingStartedEvent>,
+) {
+    for event in swing_events.read() {
+        event_log.write(
+            time.elapsed_secs(),
+            GameplayEvent::SwingStarted {
+                sword: event.sword.to_bits(),
+                swing_type: format!("{:?}", event.swing_type),
+                charge: event.charge,
+                speed: event.speed,
+            },
+        );
+    }
+}
+
+fn log_swing_phases(
+    time: Res<Time>,
+    mut event_log: ResMut<EventLog>,
+    mut phase_events: EventReader<SwingPhaseChangedEvent>,
+) {
+    for event in phase_events.read() {
+        event_log.write(
+            time.elapsed_secs(),
+            GameplayEvent::SwingPhase {
+                sword: event.sword.to_bits(),
+                phase: format!("{:?}", event.phase),
+            },
+        );
+    }
+}
+
+fn log_swing_notifies(
+    time: Res<Time>,
+    mut event_log: ResMut<EventLog>,
+    mut notify_events: EventReader<SwingNotifyEvent>,
+) {
+    for event in notify_events.read() {
+        event_log.write(
+            time.elapsed_secs(),
+            GameplayEvent::SwingNotify {
+                sword: event.sword.to_bits(),
+                swing_type: format!("{:?}", event.swing_type),
+                name: event.name.clone(),
+            },
+        );
+    }
+}
+
+fn log_swing_finishes(
+    time: Res<Time>,
+    mut event_log: ResMut<EventLog>,
+    mut finished_events: EventReader<SwingFinishedEvent>,
+) {
+    for event in finished_events.read() {
+        event_log.write(
+            time.elapsed_secs(),
+            GameplayEvent::SwingFinished {
+                sword: event.sword.to_bits(),
+                interrupted: event.interrupted,
+            },
+        );
+    }
+}
+
+fn log_hits(
+    time: Res<Time>,
+    mut event_log: ResMut<EventLog>,
+    mut damage_events: EventReader<DamageEvent>,
+    resistance_query: Query<&Resistances>,
+) {
+    for event in damage_events.read() {
+        event_log.write(
+            time.elapsed_secs(),
+            GameplayEvent::Hit {
+                target: event.target.to_bits(),
+                source: event.source.map(Entity::to_bits),
+                amount: event.resisted_amount(resistance_query.get(event.target).ok()),
+                raw_amount: event.amount,
+                damage_type: format!("{:?}", event.damage_type),
+                crit: event.crit,
+                sweet_spot: event.sweet_spot,
+                x: event.position.x,
+                y: event.position.y,
+            },
+        );
+    }
+}
+
+fn log_deaths(
+    time: Res<Time>,
+    mut event_log: ResMut<EventLog>,
+    dying_query: Query<(Entity, &Transform), Added<Dying>>,
+) {
+    for (entity, transform) in dying_query.iter() {
+        event_log.write(
+            time.elapsed_secs(),
+            GameplayEvent::Death {
+                enemy: entity.to_bits(),
+                x: transform.translation.x,
+                y: transform.translation.y,
+            },
+        );
+    }
+}
+
+// Keep the file current so it can be read while the game runs or after a crash
+fn flush_event_log(mut event_log: ResMut<EventLog>) {
+    if let Err(error) = event_log.writer.flush() {
+        warn!("Failed to flush event log: {}", error);
+    }
+}
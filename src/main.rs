@@ -1,33 +1,126 @@
+// Bevy systems take their queries and resources as arguments, which these lints count against
+#![allow(clippy::type_complexity, clippy::too_many_arguments)]
+
+mod arena;
+mod audio;
+#[allow(dead_code)] // CursorPlugin is switched off below
 mod cursor;
+mod curve;
+mod damage;
+mod debug_overlay;
+mod dash;
+mod effects;
 mod enemy;
+mod gamepad;
+mod hud;
+mod input_map;
+mod logging;
+mod parry;
+mod particles;
 mod player;
+mod projectile;
+mod rebind_menu;
+mod score;
+mod status;
 mod swing_animation;
+mod swing_editor;
 mod sword;
 mod sword_collider;
+mod sword_formation;
+mod sword_sprite;
+mod sword_throw;
+mod trail;
+mod weapon;
 
-use crate::cursor::CursorPlugin;
+use crate::arena::ArenaPlugin;
+use crate::audio::GameAudioPlugin;
+use crate::damage::DamagePlugin;
+use crate::debug_overlay::DebugOverlayPlugin;
+use crate::dash::DashPlugin;
+use crate::effects::EffectsPlugin;
 use crate::enemy::{EnemyPlugin, EnemySpawner};
+use crate::gamepad::GamepadPlugin;
+use crate::hud::HudPlugin;
+use crate::input_map::{Action, ActionState, InputMapPlugin};
+use crate::logging::{LoggingConfig, LoggingPlugin, LOGGING_CONFIG_PATH};
+use crate::parry::ParryPlugin;
+use crate::particles::ParticlePlugin;
 use crate::player::PlayerPlugin;
+use crate::projectile::ProjectilePlugin;
+use crate::rebind_menu::RebindMenuPlugin;
+use crate::score::ScorePlugin;
+use crate::status::StatusPlugin;
 use crate::swing_animation::SwingAnimationPlugin;
+use crate::swing_editor::SwingEditorPlugin;
 use crate::sword::SwordPlugin;
 use crate::sword_collider::SwordColliderPlugin;
+use crate::sword_formation::SwordFormationPlugin;
+use crate::sword_sprite::SwordSpritePlugin;
+use crate::sword_throw::SwordThrowPlugin;
+use crate::trail::TrailPlugin;
+use crate::weapon::WeaponPlugin;
+use bevy::log::LogPlugin;
 use bevy::prelude::*;
 
 fn main() {
+    let logging = LoggingConfig::load_or_default(LOGGING_CONFIG_PATH);
+
     App::new()
-        .add_plugins(DefaultPlugins)
+        .add_plugins(DefaultPlugins.set(LogPlugin {
+            filter: logging.filter.clone(),
+            ..default()
+        }))
         .add_plugins((
-            // CursorPlugin, // Handles cursor hiding/showing
+            // cursor::CursorPlugin, // Handles cursor hiding/showing
             SwordPlugin,  // Handles sword following mouse
             SwordColliderPlugin,
+            SwordFormationPlugin, // Multiple swords, formations and summoning
+            SwordThrowPlugin,     // Throw and recall swords as projectiles
+            ArenaPlugin,
             PlayerPlugin,
             SwingAnimationPlugin,
-            // EnemyPlugin,
+            TrailPlugin, // Afterimages behind swings
+            ParryPlugin, // Deflect window against enemy attacks
+            ProjectilePlugin,
+            EnemyPlugin,
+        ))
+        .add_plugins((
+            GamepadPlugin,    // Sticks and buttons alongside keyboard/mouse
+            InputMapPlugin,   // Actions and rebindable bindings
+            RebindMenuPlugin, // In-game bindings editor
+            DashPlugin,
+            ScorePlugin,
+            DamagePlugin, // Applies hits, damage numbers and enemy health bars
+            ParticlePlugin,
+            EffectsPlugin, // Hit sparks, flashes and death dissolves
+            GameAudioPlugin, // Sound effects and crossfading music
+            LoggingPlugin {
+                event_log: logging.event_log,
+            },
+            DebugOverlayPlugin, // Collider outlines, swing paths and live tuning
+            SwingEditorPlugin,  // Pauses to edit and save swing curves
+            HudPlugin, // Health, score, waves and cooldowns
+        ))
+        .add_plugins((
+            WeaponPlugin,      // Weapon definitions and swapping
+            SwordSpritePlugin, // Sprite sheet frames driven by the swing
+            StatusPlugin,      // Burns, chills, poison and lightning arcs
         ))
         .add_systems(Startup, setup)
+        .add_systems(Update, toggle_pause)
         .run();
 }
 
+fn toggle_pause(actions: Res<ActionState>, mut time: ResMut<Time<Virtual>>) {
+    if actions.just_pressed(Action::Pause) {
+        if time.is_paused() {
+            time.unpause();
+        } else {
+            time.pause();
+        }
+    }
+}
+
 fn setup(mut commands: Commands) {
     commands.spawn(Camera2d);
 
@@ -0,0 +1,197 @@
+use crate::audio::SoundEvent;
+use crate::enemy::{AttackKind, Enemy, EnemyAttack, Staggered};
+use crate::input_map::{Action, ActionState};
+use crate::projectile::{Projectile, ProjectileOwner};
+use crate::swing_animation::{animate_sword_swing, SwingAnimation, SwingInterrupt};
+use crate::sword::{Sword, SwordNode};
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use std::f32::consts::PI;
+
+const PARRY_WINDOW: f32 = 0.25; // Seconds the deflect window stays open
+const PARRY_COOLDOWN: f32 = 0.4; // Seconds before the sword can parry again
+const STAGGER_DURATION: f32 = 1.0;
+const REFLECT_SPEED_MULTIPLIER: f32 = 1.5;
+pub const PARRY_SOUND: &str = "audio/parry_clang.ogg";
+
+pub struct ParryPlugin;
+
+impl Plugin for ParryPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                handle_parry_input,
+                update_stance,
+                handle_parry_contacts,
+                // Guard pose overrides the idle pose the swing animation just wrote
+                apply_guard_pose.after(animate_sword_swing),
+            )
+                .chain(),
+        );
+    }
+}
+
+// Defensive state of a sword, tracked alongside its SwingPhase
+#[derive(Component, Default)]
+pub enum SwordStance {
+    #[default]
+    Ready,
+    Parrying {
+        window: Timer, // Deflect window, projectiles and attackers touching the blade are parried
+    },
+    Recovering {
+        cooldown: Timer,
+    },
+}
+
+impl SwordStance {
+    pub fn is_parrying(&self) -> bool {
+        matches!(self, SwordStance::Parrying { .. })
+    }
+}
+
+fn handle_parry_input(
+    actions: Res<ActionState>,
+    node_query: Query<&Children, With<SwordNode>>,
+    mut sword_query: Query<(&mut SwingAnimation, &mut SwordStance), With<Sword>>,
+) {
+    if !actions.just_pressed(Action::Parry) {
+        return;
+    }
+
+    // Every sword on the node raises its guard, breaking off windups and recoveries
+    for children in node_query.iter() {
+        for child in children.iter() {
+            let Ok((mut swing, mut stance)) = sword_query.get_mut(child) else {
+                continue;
+            };
+            if matches!(*stance, SwordStance::Ready) && swing.interrupt(SwingInterrupt::Parry) {
+                *stance = SwordStance::Parrying {
+                    window: Timer::from_seconds(PARRY_WINDOW, TimerMode::Once),
+                };
+            }
+        }
+    }
+}
+
+fn update_stance(time: Res<Time>, mut stance_query: Query<&mut SwordStance>) {
+    for mut stance in stance_query.iter_mut() {
+        match stance.as_mut() {
+            SwordStance::Ready => {}
+            SwordStance::Parrying { window } => {
+                window.tick(time.delta());
+                if window.finished() {
+                    *stance = SwordStance::Recovering {
+                        cooldown: Timer::from_seconds(PARRY_COOLDOWN, TimerMode::Once),
+                    };
+                }
+            }
+            SwordStance::Recovering { cooldown } => {
+                cooldown.tick(time.delta());
+                if cooldown.finished() {
+                    *stance = SwordStance::Ready;
+                }
+            }
+        }
+    }
+}
+
+// Reflect enemy projectiles and stagger melee attackers touching a parrying sword. Overlaps
+// are polled like blade hits, so whatever the blade already touched when the window opened
+// is parried too.
+fn handle_parry_contacts(
+    mut commands: Commands,
+    rapier_context: ReadRapierContext,
+    sword_query: Query<(Entity, &SwordStance), With<Sword>>,
+    mut projectile_query: Query<(&mut Projectile, &mut Velocity, &Transform)>,
+    enemy_query: Query<(&EnemyAttack, &Transform, Has<Staggered>), With<Enemy>>,
+    mut sound_events: EventWriter<SoundEvent>,
+) {
+    let Ok(rapier_context) = rapier_context.single() else {
+        return;
+    };
+    // Staggers only land once the commands run, so two blades could both catch one attacker
+    let mut staggered: Vec<Entity> = Vec::new();
+
+    for (sword_entity, stance) in sword_query.iter() {
+        if !stance.is_parrying() {
+            continue;
+        }
+        for (entity1, entity2, intersecting) in rapier_context.intersection_pairs_with(sword_entity)
+        {
+            if !intersecting {
+                continue;
+            }
+            let other = if entity1 == sword_entity { entity2 } else { entity1 };
+            parry_contact(
+                &mut commands,
+                other,
+                &mut projectile_query,
+                &enemy_query,
+                &mut staggered,
+                &mut sound_events,
+            );
+        }
+    }
+}
+
+fn parry_contact(
+    commands: &mut Commands,
+    other: Entity,
+    projectile_query: &mut Query<(&mut Projectile, &mut Velocity, &Transform)>,
+    enemy_query: &Query<(&EnemyAttack, &Transform, Has<Staggered>), With<Enemy>>,
+    staggered: &mut Vec<Entity>,
+    sound_events: &mut EventWriter<SoundEvent>,
+) {
+    if let Ok((mut projectile, mut velocity, transform)) = projectile_query.get_mut(other) {
+        // Reflected projectiles belong to the player, so each is only sent back once
+        if projectile.owner != ProjectileOwner::Enemy {
+            return;
+        }
+        // Send it back at whoever fired it, or straight back if they are gone
+        let speed = velocity.linvel.length() * REFLECT_SPEED_MULTIPLIER;
+        let direction = projectile
+            .shooter
+            .and_then(|shooter| enemy_query.get(shooter).ok())
+            .map(|(_, shooter_transform, _)| {
+                (shooter_transform.translation - transform.translation)
+                    .truncate()
+                    .normalize_or_zero()
+            })
+            .unwrap_or(-velocity.linvel.normalize_or_zero());
+        velocity.linvel = direction * speed;
+        projectile.owner = ProjectileOwner::Player;
+        projectile.lifetime.reset();
+        sound_events.write(SoundEvent::new(PARRY_SOUND));
+    } else if let Ok((attack, _, already_staggered)) = enemy_query.get(other) {
+        // Held against the blade, an attacker is only staggered once per parry
+        if attack.kind != AttackKind::Melee || already_staggered || staggered.contains(&other) {
+            return;
+        }
+        commands.entity(other).try_insert(Staggered {
+            timer: Timer::from_seconds(STAGGER_DURATION, TimerMode::Once),
+        });
+        staggered.push(other);
+        sound_events.write(SoundEvent::new(PARRY_SOUND));
+    }
+}
+
+// Hold parrying swords across the front of the node with the blade horizontal
+fn apply_guard_pose(
+    node_query: Query<(&SwordNode, &Children)>,
+    mut sword_query: Query<(&Sword, &SwordStance, &mut Transform)>,
+) {
+    for (sword_node, children) in node_query.iter() {
+        for child in children.iter() {
+            if let Ok((sword, stance, mut transform)) = sword_query.get_mut(child) {
+                if !stance.is_parrying() {
+                    continue;
+                }
+                let guard_pos = Vec2::new(0.0, 10.0 + sword.slot as f32 * 12.0);
+                transform.translation = guard_pos.extend(transform.translation.z);
+                transform.rotation = Quat::from_rotation_z(-PI * 0.5 * sword_node.facing);
+            }
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use bevy::prelude::*;
+use rand::Rng;
+use std::ops::Range;
+
+pub struct ParticlePlugin;
+
+impl Plugin for ParticlePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, update_particles);
+    }
+}
+
+// A single CPU-simulated particle, drawn as a plain colored sprite stretched along its velocity
+#[derive(Component)]
+pub struct Particle {
+    pub velocity: Vec2,
+    pub drag: f32, // Fraction of velocity lost per second
+    pub timer: Timer,
+    pub color: Color,
+}
+
+// Describes a one-shot burst of particles; emit() spawns them
+#[derive(Clone)]
+pub struct ParticleBurst {
+    pub count: usize,
+    pub color: Color,
+    pub direction: Vec2, // Zero for a burst in every direction
+    pub spread: f32,     // Half-angle of the cone around `direction` (radians)
+    pub speed: Range<f32>,
+    pub lifetime: Range<f32>,
+    pub size: Vec2, // Length along the velocity, then width
+    pub drag: f32,
+}
+
+impl Default for ParticleBurst {
+    fn default() -> Self {
+        Self {
+            count: 8,
+            color: Color::WHITE,
+            direction: Vec2::ZERO,
+            spread: std::f32::consts::PI,
+            speed: 100.0..200.0,
+            lifetime: 0.2..0.4,
+            size: Vec2::new(4.0, 4.0),
+            drag: 4.0,
+        }
+    }
+}
+
+impl ParticleBurst {
+    pub fn emit(&self, commands: &mut Commands, position: Vec2) {
+        let mut rng = rand::thread_rng();
+        let base_angle = if self.direction == Vec2::ZERO {
+            0.0
+        } else {
+            self.direction.to_angle()
+        };
+
+        for _ in 0..self.count {
+            let angle = if self.direction == Vec2::ZERO {
+                rng.gen_range(0.0..std::f32::consts::TAU)
+            } else {
+                base_angle + rng.gen_range(-self.spread..=self.spread)
+            };
+            let velocity = Vec2::from_angle(angle) * rng.gen_range(self.speed.clone());
+            let lifetime = rng.gen_range(self.lifetime.clone());
+
+            commands.spawn((
+                Sprite::from_color(self.color, self.size),
+                Transform::from_translation(position.extend(5.0))
+                    .with_rotation(Quat::from_rotation_z(angle)),
+                Particle {
+                    velocity,
+                    drag: self.drag,
+                    timer: Timer::from_seconds(lifetime, TimerMode::Once),
+                    color: self.color,
+                },
+            ));
+        }
+    }
+}
+
+fn update_particles(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut particle_query: Query<(Entity, &mut Particle, &mut Transform, &mut Sprite)>,
+) {
+    let delta = time.delta_secs();
+    for (entity, mut particle, mut transform, mut sprite) in particle_query.iter_mut() {
+        particle.timer.tick(time.delta());
+        if particle.timer.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        transform.translation += (particle.velocity * delta).extend(0.0);
+        let drag = particle.drag;
+        particle.velocity *= (1.0 - drag * delta).max(0.0);
+
+        // Shrink and fade out over the particle's life
+        let remaining = particle.timer.fraction_remaining();
+        transform.scale = Vec3::new(remaining, remaining, 1.0);
+        sprite.color = particle.color.with_alpha(remaining);
+    }
+}
@@ -1,10 +1,43 @@
+use crate::dash::Dash;
+use crate::input_map::ActionState;
 use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use std::f32::consts::FRAC_PI_2;
 
-pub const MOVEMENT_SPEED: f32 = 50.0;
+pub const MOVEMENT_SPEED: f32 = 250.0;
+pub const ACCELERATION: f32 = 2000.0; // px/s² towards the input direction
+pub const FRICTION: f32 = 1600.0; // px/s² of deceleration with no input
 
 #[derive(Component)]
 pub struct PlayerMovement {
-    pub speed: f32,
+    pub speed: f32,        // Max speed at full input
+    pub acceleration: f32,
+    pub friction: f32,
+    pub velocity: Vec2,    // Current velocity, integrated by move_player
+}
+
+#[derive(Component)]
+pub struct PlayerHealth {
+    pub current: f32,
+    pub max: f32,
+}
+
+impl PlayerHealth {
+    pub fn take_damage(&mut self, amount: f32) {
+        self.current = (self.current - amount).max(0.0);
+    }
+}
+
+// Stats upgrades raise over a run
+#[derive(Component)]
+pub struct PlayerStats {
+    pub attack_speed: f32, // Swing playback multiplier, 1.0 = base speed
+}
+
+impl Default for PlayerStats {
+    fn default() -> Self {
+        Self { attack_speed: 1.0 }
+    }
 }
 
 pub struct PlayerPlugin;
@@ -23,31 +56,75 @@ fn spawn_player(
 ) {
     commands.spawn((
         Mesh2d(meshes.add(Circle::default())),
-        MeshMaterial2d(materials.add(Color::from(Color::srgb(0.0, 0.0, 1.0)))), // BLUE
+        MeshMaterial2d(materials.add(Color::srgb(0.0, 0.0, 1.0))), // BLUE
         Transform::default().with_scale(Vec3::splat(30.)),
         PlayerMovement {
             speed: MOVEMENT_SPEED,
+            acceleration: ACCELERATION,
+            friction: FRICTION,
+            velocity: Vec2::ZERO,
         },
+        PlayerHealth {
+            current: 100.0,
+            max: 100.0,
+        },
+        PlayerStats::default(),
+        // Lets enemy projectiles register hits on the player and stops it at walls and enemies
+        RigidBody::KinematicPositionBased,
+        Collider::ball(0.5),
+        SolverGroups::default(), // Enemies are taken out while dashing
+        KinematicCharacterController {
+            // Top-down: there is no ground to snap to or slopes to slide down
+            snap_to_ground: None,
+            autostep: None,
+            max_slope_climb_angle: FRAC_PI_2,
+            min_slope_slide_angle: FRAC_PI_2,
+            ..default()
+        },
+        Dash::default(),
     ));
 }
 
 pub fn move_player(
     time: Res<Time>,
-    keys: Res<ButtonInput<KeyCode>>,
-    mut query: Query<(&PlayerMovement, &mut Transform)>,
+    actions: Res<ActionState>,
+    mut query: Query<(
+        &mut PlayerMovement,
+        &mut KinematicCharacterController,
+        Option<&KinematicCharacterControllerOutput>,
+    )>,
 ) {
-    for (player_movement, mut transform) in query.iter_mut() {
-        if keys.pressed(KeyCode::KeyW) || keys.pressed(KeyCode::ArrowUp) {
-            transform.translation.y += player_movement.speed * time.delta_secs();
-        }
-        if keys.pressed(KeyCode::KeyA) || keys.pressed(KeyCode::ArrowLeft) {
-            transform.translation.x -= player_movement.speed * time.delta_secs();
-        }
-        if keys.pressed(KeyCode::KeyS) || keys.pressed(KeyCode::ArrowDown) {
-            transform.translation.y -= player_movement.speed * time.delta_secs();
-        }
-        if keys.pressed(KeyCode::KeyD) || keys.pressed(KeyCode::ArrowRight) {
-            transform.translation.x += player_movement.speed * time.delta_secs();
+    let dt = time.delta_secs();
+    if dt <= 0.0 {
+        return;
+    }
+
+    for (mut player_movement, mut controller, output) in query.iter_mut() {
+        // Anything that stopped last frame's move (walls, enemies) also eats that velocity
+        let blocked = output
+            .filter(|o| o.effective_translation.length() < o.desired_translation.length());
+        if let Some(output) = blocked {
+            player_movement.velocity = output.effective_translation / dt;
         }
+
+        // Diagonals are no faster than straight lines, a partially tilted stick walks slower
+        let input = actions.move_axis.clamp_length_max(1.0);
+        let (target, rate) = if input != Vec2::ZERO {
+            (input * player_movement.speed, player_movement.acceleration)
+        } else {
+            (Vec2::ZERO, player_movement.friction)
+        };
+        player_movement.velocity = move_towards(player_movement.velocity, target, rate * dt);
+
+        controller.translation = Some(player_movement.velocity * dt);
+    }
+}
+
+fn move_towards(current: Vec2, target: Vec2, max_delta: f32) -> Vec2 {
+    let delta = target - current;
+    if delta.length() <= max_delta {
+        target
+    } else {
+        current + delta.normalize() * max_delta
     }
 }
@@ -0,0 +1,144 @@
+use crate::arena::Wall;
+use crate::damage::{DamageEvent, DamageType};
+use crate::dash::Invulnerable;
+use crate::enemy::Enemy;
+use crate::player::{PlayerHealth, PlayerMovement};
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+const PROJECTILE_LIFETIME: f32 = 4.0;
+
+pub struct ProjectilePlugin;
+
+impl Plugin for ProjectilePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (handle_projectile_collisions, expire_projectiles));
+    }
+}
+
+// Which side a projectile is on; a parried projectile changes sides
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ProjectileOwner {
+    Enemy,
+    Player,
+}
+
+#[derive(Component)]
+pub struct Projectile {
+    pub owner: ProjectileOwner,
+    pub shooter: Option<Entity>, // Entity that fired it, used to aim reflections back
+    pub damage: f32,
+    pub lifetime: Timer,
+}
+
+impl Projectile {
+    pub fn new(owner: ProjectileOwner, shooter: Option<Entity>, damage: f32) -> Self {
+        Self {
+            owner,
+            shooter,
+            damage,
+            lifetime: Timer::from_seconds(PROJECTILE_LIFETIME, TimerMode::Once),
+        }
+    }
+}
+
+pub fn spawn_projectile(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+    position: Vec2,
+    velocity: Vec2,
+    projectile: Projectile,
+) -> Entity {
+    commands
+        .spawn((
+            projectile,
+            Mesh2d(meshes.add(Circle::default())),
+            MeshMaterial2d(materials.add(Color::srgb(1.0, 0.6, 0.1))), // Orange
+            Transform::from_xyz(position.x, position.y, 0.5).with_scale(Vec3::splat(10.)),
+            RigidBody::KinematicVelocityBased,
+            Velocity::linear(velocity),
+            Collider::ball(0.5),
+            // Hits are picked up from collision events, so nothing needs to be pushed around
+            Sensor,
+            // Projectiles need to see the kinematic player and sword as well as walls
+            ActiveCollisionTypes::all(),
+            ActiveEvents::COLLISION_EVENTS,
+        ))
+        .id()
+}
+
+fn handle_projectile_collisions(
+    mut commands: Commands,
+    mut collision_events: EventReader<CollisionEvent>,
+    projectile_query: Query<(&Projectile, &Transform, &Velocity)>,
+    enemy_query: Query<(), With<Enemy>>,
+    player_query: Query<Has<Invulnerable>, (With<PlayerHealth>, With<PlayerMovement>)>,
+    wall_query: Query<(), With<Wall>>,
+    mut damage_events: EventWriter<DamageEvent>,
+) {
+    for collision_event in collision_events.read() {
+        let CollisionEvent::Started(entity1, entity2, _) = collision_event else {
+            continue;
+        };
+        let (projectile_entity, other) = if projectile_query.contains(*entity1) {
+            (*entity1, *entity2)
+        } else if projectile_query.contains(*entity2) {
+            (*entity2, *entity1)
+        } else {
+            continue;
+        };
+        let Ok((projectile, transform, velocity)) = projectile_query.get(projectile_entity) else {
+            continue;
+        };
+        let damage = DamageEvent {
+            target: other,
+            source: projectile.shooter,
+            amount: projectile.damage,
+            damage_type: DamageType::Projectile,
+            crit: false,
+            sweet_spot: false,
+            position: transform.translation.truncate(),
+            direction: velocity.linvel.normalize_or_zero(),
+            indirect: false,
+        };
+
+        match projectile.owner {
+            ProjectileOwner::Enemy => {
+                if let Ok(invulnerable) = player_query.get(other) {
+                    // Dodged projectiles fly on through the player
+                    if invulnerable {
+                        continue;
+                    }
+                    damage_events.write(damage);
+                    commands.entity(projectile_entity).try_despawn();
+                    continue;
+                }
+            }
+            ProjectileOwner::Player => {
+                if enemy_query.contains(other) {
+                    damage_events.write(damage);
+                    commands.entity(projectile_entity).try_despawn();
+                    continue;
+                }
+            }
+        }
+
+        if wall_query.contains(other) {
+            commands.entity(projectile_entity).try_despawn();
+        }
+    }
+}
+
+fn expire_projectiles(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut projectile_query: Query<(Entity, &mut Projectile)>,
+) {
+    for (entity, mut projectile) in projectile_query.iter_mut() {
+        projectile.lifetime.tick(time.delta());
+        if projectile.lifetime.finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
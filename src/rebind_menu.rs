@@ -0,0 +1,198 @@
+use crate::audio::{AudioSettings, VolumeChannel, AUDIO_SETTINGS_PATH};
+use crate::input_map::{Action, ActionState, InputMap, Rebinding, BINDINGS_PATH};
+use bevy::prelude::*;
+
+pub struct RebindMenuPlugin;
+
+impl Plugin for RebindMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (toggle_bindings_menu, handle_menu_buttons, refresh_bindings_menu).chain(),
+        );
+    }
+}
+
+#[derive(Component)]
+struct BindingsMenu;
+
+#[derive(Component, Clone, Copy)]
+enum MenuButton {
+    Add(Action),   // Capture the next input as an extra binding
+    Clear(Action), // Remove every binding of the action
+    ResetDefaults,
+    Volume(VolumeChannel, i32), // Steps up or down
+}
+
+fn toggle_bindings_menu(actions: Res<ActionState>, mut rebinding: ResMut<Rebinding>) {
+    if actions.just_pressed(Action::ToggleBindingsMenu) {
+        rebinding.menu_open = !rebinding.menu_open;
+        rebinding.cancel();
+    }
+}
+
+fn handle_menu_buttons(
+    button_query: Query<(&Interaction, &MenuButton), Changed<Interaction>>,
+    mut rebinding: ResMut<Rebinding>,
+    mut input_map: ResMut<InputMap>,
+    mut audio_settings: ResMut<AudioSettings>,
+) {
+    for (interaction, button) in button_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match *button {
+            MenuButton::Volume(channel, steps) => {
+                audio_settings.adjust(channel, steps);
+                if let Err(error) = audio_settings.save(AUDIO_SETTINGS_PATH) {
+                    warn!(target: "audio", "Failed to save audio settings: {}", error);
+                }
+                continue;
+            }
+            MenuButton::Add(action) => {
+                rebinding.start(action);
+                continue; // Saved once the binding is captured
+            }
+            MenuButton::Clear(action) => input_map.clear_bindings(action),
+            MenuButton::ResetDefaults => *input_map = InputMap::default(),
+        }
+        if let Err(error) = input_map.save(BINDINGS_PATH) {
+            warn!(target: "input", "Failed to save input bindings: {}", error);
+        }
+    }
+}
+
+// Rebuild the menu whenever it opens, closes or the bindings or volumes it shows change
+fn refresh_bindings_menu(
+    mut commands: Commands,
+    rebinding: Res<Rebinding>,
+    input_map: Res<InputMap>,
+    audio_settings: Res<AudioSettings>,
+    menu_query: Query<Entity, With<BindingsMenu>>,
+) {
+    if !rebinding.is_changed() && !input_map.is_changed() && !audio_settings.is_changed() {
+        return;
+    }
+    for menu in menu_query.iter() {
+        commands.entity(menu).despawn();
+    }
+    if rebinding.menu_open {
+        spawn_bindings_menu(&mut commands, &input_map, &rebinding, &audio_settings);
+    }
+}
+
+fn spawn_bindings_menu(
+    commands: &mut Commands,
+    input_map: &InputMap,
+    rebinding: &Rebinding,
+    audio_settings: &AudioSettings,
+) {
+    let text_font = TextFont {
+        font_size: 14.0,
+        ..default()
+    };
+
+    // Centred, clear of the HUD in the top left corner
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            Pickable::IGNORE, // Only the panel itself catches the cursor
+            BindingsMenu,
+        ))
+        .with_children(|root| {
+            root.spawn((
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(4.0),
+                    padding: UiRect::all(Val::Px(12.0)),
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.85)),
+            ))
+            .with_children(|menu| {
+                let title = "Input bindings and volume (F1 to close)";
+                menu.spawn((Text::new(title), text_font.clone()));
+
+                for action in Action::ALL {
+                    let bindings = if rebinding.action == Some(action) {
+                        "press any input... (Esc to cancel)".to_string()
+                    } else {
+                        input_map
+                            .bindings(action)
+                            .iter()
+                            .map(|b| b.to_string())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    };
+
+                    menu.spawn(Node {
+                        column_gap: Val::Px(8.0),
+                        align_items: AlignItems::Center,
+                        ..default()
+                    })
+                    .with_children(|row| {
+                        row.spawn((
+                            Text::new(format!("{:?}: {}", action, bindings)),
+                            text_font.clone(),
+                            Node {
+                                width: Val::Px(520.0),
+                                ..default()
+                            },
+                        ));
+                        spawn_menu_button(row, "Add", MenuButton::Add(action), &text_font);
+                        spawn_menu_button(row, "Clear", MenuButton::Clear(action), &text_font);
+                    });
+                }
+
+                let reset = MenuButton::ResetDefaults;
+                spawn_menu_button(menu, "Reset to defaults", reset, &text_font);
+
+                for channel in VolumeChannel::ALL {
+                    menu.spawn(Node {
+                        column_gap: Val::Px(8.0),
+                        align_items: AlignItems::Center,
+                        ..default()
+                    })
+                    .with_children(|row| {
+                        let percent = (audio_settings.volume(channel) * 100.0).round();
+                        row.spawn((
+                            Text::new(format!("{:?} volume: {}%", channel, percent)),
+                            text_font.clone(),
+                            Node {
+                                width: Val::Px(520.0),
+                                ..default()
+                            },
+                        ));
+                        spawn_menu_button(row, "-", MenuButton::Volume(channel, -1), &text_font);
+                        spawn_menu_button(row, "+", MenuButton::Volume(channel, 1), &text_font);
+                    });
+                }
+            });
+        });
+}
+
+fn spawn_menu_button(
+    parent: &mut ChildSpawnerCommands,
+    label: &str,
+    button: MenuButton,
+    text_font: &TextFont,
+) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                padding: UiRect::axes(Val::Px(8.0), Val::Px(2.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.25, 0.25, 0.3)),
+            button,
+        ))
+        .with_child((Text::new(label), text_font.clone()));
+}
@@ -0,0 +1,58 @@
+use bevy::prelude::*;
+
+const COMBO_WINDOW: f32 = 2.0; // Seconds without a hit before the combo drops
+const KILL_POINTS: u32 = 100;
+const COMBO_BONUS_PER_HIT: u32 = 10;
+
+pub struct ScorePlugin;
+
+impl Plugin for ScorePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Score>()
+            .add_systems(Update, decay_combo);
+    }
+}
+
+#[derive(Resource)]
+pub struct Score {
+    pub points: u32,
+    pub combo: u32,
+    pub best_combo: u32,
+    pub combo_timer: Timer, // Counts down the window to land the next hit
+}
+
+impl Default for Score {
+    fn default() -> Self {
+        let mut combo_timer = Timer::from_seconds(COMBO_WINDOW, TimerMode::Once);
+        combo_timer.tick(combo_timer.duration());
+        Self {
+            points: 0,
+            combo: 0,
+            best_combo: 0,
+            combo_timer,
+        }
+    }
+}
+
+impl Score {
+    pub fn register_hit(&mut self) {
+        self.combo += 1;
+        self.best_combo = self.best_combo.max(self.combo);
+        self.combo_timer.reset();
+    }
+
+    // Kills are worth more the longer the current combo
+    pub fn register_kill(&mut self) {
+        self.points += KILL_POINTS + self.combo * COMBO_BONUS_PER_HIT;
+    }
+}
+
+fn decay_combo(time: Res<Time>, mut score: ResMut<Score>) {
+    if score.combo == 0 {
+        return;
+    }
+    score.combo_timer.tick(time.delta());
+    if score.combo_timer.finished() {
+        score.combo = 0;
+    }
+}
@@ -0,0 +1,369 @@
+use crate::damage::{DamageEvent, DamageType, Resistances};
+use crate::effects::{Dying, HitFlash};
+use crate::enemy::Enemy;
+use crate::particles::ParticleBurst;
+use crate::trail::spawn_afterimage;
+use bevy::prelude::*;
+use std::time::Duration;
+
+const STATUS_TICK: f32 = 0.5; // Seconds between burn and poison damage ticks
+
+const BURN_DURATION: f32 = 2.0;
+const BURN_MAX_STACKS: u32 = 3;
+const BURN_DAMAGE: f32 = 5.0; // Per stack per tick
+
+const POISON_DURATION: f32 = 5.0;
+const POISON_MAX_STACKS: u32 = 8;
+const POISON_DAMAGE: f32 = 1.5;
+
+const CHILL_DURATION: f32 = 2.5;
+const CHILL_MAX_STACKS: u32 = 3;
+const CHILL_SLOW: f32 = 0.2; // Speed lost per stack
+
+const CHAIN_RADIUS: f32 = 150.0;
+const CHAIN_TARGETS: usize = 3;
+const CHAIN_FALLOFF: f32 = 0.5; // Share of the hit each arc carries
+const CHAIN_ARC_LIFETIME: f32 = 0.12;
+
+pub struct StatusPlugin;
+
+impl Plugin for StatusPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<LightningArcEvent>()
+            .add_systems(
+                Update,
+                (apply_statuses, arc_lightning, tick_damage_over_time, apply_chill).chain(),
+            )
+            // After hit flashes have been inserted, so a flash isn't painted over
+            .add_systems(PostUpdate, tint_affected_enemies);
+    }
+}
+
+// Stacks of one status effect. Each application adds a stack, up to `max`, and restarts
+// the duration; the stacks all expire together.
+#[derive(Clone, Debug)]
+pub struct StatusStacks {
+    pub count: u32,
+    max: u32,
+    duration: Timer,
+}
+
+impl StatusStacks {
+    fn new(max: u32, duration: f32) -> Self {
+        Self {
+            count: 1,
+            max,
+            duration: Timer::from_seconds(duration, TimerMode::Once),
+        }
+    }
+
+    fn add(&mut self) {
+        self.count = (self.count + 1).min(self.max);
+        self.duration.reset();
+    }
+
+    // Returns whether the stacks have run out
+    fn tick(&mut self, delta: Duration) -> bool {
+        self.duration.tick(delta);
+        self.duration.finished()
+    }
+}
+
+// Stacking damage dealt every STATUS_TICK
+#[derive(Clone, Debug)]
+pub struct DamageOverTime {
+    pub stacks: StatusStacks,
+    per_stack: f32,
+    tick: Timer,
+    source: Option<Entity>, // Whatever applied the latest stack
+}
+
+impl DamageOverTime {
+    fn new(max: u32, duration: f32, per_stack: f32, source: Option<Entity>) -> Self {
+        Self {
+            stacks: StatusStacks::new(max, duration),
+            per_stack,
+            tick: Timer::from_seconds(STATUS_TICK, TimerMode::Repeating),
+            source,
+        }
+    }
+
+    fn add(&mut self, source: Option<Entity>) {
+        self.stacks.add();
+        self.source = source;
+    }
+
+    // Damage due this frame, if a tick came up
+    fn tick(&mut self, delta: Duration) -> Option<f32> {
+        self.tick.tick(delta);
+        let ticks = self.tick.times_finished_this_tick();
+        (ticks > 0).then_some(ticks as f32 * self.per_stack * self.stacks.count as f32)
+    }
+}
+
+#[derive(Component)]
+pub struct Burning(pub DamageOverTime);
+
+#[derive(Component)]
+pub struct Poisoned(pub DamageOverTime);
+
+// Slowed by CHILL_SLOW per stack
+#[derive(Component)]
+pub struct Chilled {
+    pub stacks: StatusStacks,
+}
+
+// A lightning hit arcing on from `origin` to the enemies around it
+#[derive(Event)]
+struct LightningArcEvent {
+    origin: Entity,
+    source: Option<Entity>,
+    position: Vec2,
+    amount: f32,
+}
+
+// Hits set off the status of their damage type; ticks and arcs never do
+fn apply_statuses(
+    mut commands: Commands,
+    mut damage_events: EventReader<DamageEvent>,
+    mut enemy_query: Query<
+        (
+            &Enemy,
+            Option<&Resistances>,
+            Option<&mut Burning>,
+            Option<&mut Poisoned>,
+            Option<&mut Chilled>,
+        ),
+        Without<Dying>,
+    >,
+    mut arc_events: EventWriter<LightningArcEvent>,
+) {
+    // Statuses first applied this frame are only inserted once the commands run
+    let mut applied: Vec<(Entity, DamageType)> = Vec::new();
+
+    for event in damage_events.read() {
+        if event.indirect {
+            continue;
+        }
+        let Ok((enemy, resistances, burning, poisoned, chilled)) =
+            enemy_query.get_mut(event.target)
+        else {
+            continue;
+        };
+        if enemy.health <= 0.0 {
+            continue;
+        }
+        let immune = resistances.is_some_and(|r| r.multiplier(event.damage_type) <= 0.0);
+        if immune || applied.contains(&(event.target, event.damage_type)) {
+            continue;
+        }
+
+        let mut target = commands.entity(event.target);
+        match event.damage_type {
+            DamageType::Physical | DamageType::Projectile => continue,
+            DamageType::Fire => match burning {
+                Some(mut burning) => burning.0.add(event.source),
+                None => {
+                    let dot = DamageOverTime::new(
+                        BURN_MAX_STACKS,
+                        BURN_DURATION,
+                        BURN_DAMAGE,
+                        event.source,
+                    );
+                    target.insert(Burning(dot));
+                }
+            },
+            DamageType::Poison => match poisoned {
+                Some(mut poisoned) => poisoned.0.add(event.source),
+                None => {
+                    let dot = DamageOverTime::new(
+                        POISON_MAX_STACKS,
+                        POISON_DURATION,
+                        POISON_DAMAGE,
+                        event.source,
+                    );
+                    target.insert(Poisoned(dot));
+                }
+            },
+            DamageType::Ice => match chilled {
+                Some(mut chilled) => chilled.stacks.add(),
+                None => {
+                    target.insert(Chilled {
+                        stacks: StatusStacks::new(CHILL_MAX_STACKS, CHILL_DURATION),
+                    });
+                }
+            },
+            DamageType::Lightning => {
+                // Carries on what got through the struck enemy; each enemy it jumps to
+                // resists its share again when the arc's damage lands
+                arc_events.write(LightningArcEvent {
+                    origin: event.target,
+                    source: event.source,
+                    position: event.position,
+                    amount: event.resisted_amount(resistances) * CHAIN_FALLOFF,
+                });
+            }
+        }
+        applied.push((event.target, event.damage_type));
+        let damage_type = event.damage_type;
+        trace!(target: "status", target = ?event.target, ?damage_type, "status applied");
+    }
+}
+
+// Lightning jumps from the enemy it struck to the nearest few around it
+fn arc_lightning(
+    mut commands: Commands,
+    mut arc_events: EventReader<LightningArcEvent>,
+    enemy_query: Query<(Entity, &Enemy, &Transform), Without<Dying>>,
+    mut damage_events: EventWriter<DamageEvent>,
+) {
+    let color = DamageType::Lightning.color();
+    for arc in arc_events.read() {
+        let Ok((_, _, origin_transform)) = enemy_query.get(arc.origin) else {
+            continue;
+        };
+        let origin = origin_transform.translation.truncate();
+
+        let mut targets: Vec<(f32, Entity, Vec2)> = enemy_query
+            .iter()
+            .filter(|(entity, enemy, _)| *entity != arc.origin && enemy.health > 0.0)
+            .map(|(entity, _, transform)| {
+                let position = transform.translation.truncate();
+                (position.distance(origin), entity, position)
+            })
+            .filter(|(distance, _, _)| *distance <= CHAIN_RADIUS)
+            .collect();
+        targets.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        for (distance, target, position) in targets.into_iter().take(CHAIN_TARGETS) {
+            let direction = (position - origin).normalize_or_zero();
+            damage_events.write(DamageEvent {
+                target,
+                source: arc.source,
+                amount: arc.amount,
+                damage_type: DamageType::Lightning,
+                crit: false,
+                sweet_spot: false,
+                position,
+                direction,
+                indirect: true,
+            });
+
+            // A thin bolt from enemy to enemy that fades straight away
+            let bolt = Sprite::from_color(Color::WHITE, Vec2::new(distance, 3.0));
+            let transform = Transform::from_translation(origin.lerp(position, 0.5).extend(5.0))
+                .with_rotation(Quat::from_rotation_z(direction.to_angle()));
+            spawn_afterimage(&mut commands, &bolt, transform, color, 1.0, CHAIN_ARC_LIFETIME);
+            ParticleBurst {
+                count: 6,
+                color,
+                speed: 60.0..160.0,
+                lifetime: 0.1..0.2,
+                size: Vec2::new(6.0, 2.0),
+                drag: 5.0,
+                ..default()
+            }
+            .emit(&mut commands, position);
+        }
+        debug!(target: "status", origin = ?arc.origin, position = ?arc.position, "lightning arc");
+    }
+}
+
+fn tick_damage_over_time(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut burning_query: Query<(Entity, &mut Burning, &Transform), Without<Dying>>,
+    mut poisoned_query: Query<(Entity, &mut Poisoned, &Transform), Without<Dying>>,
+    mut damage_events: EventWriter<DamageEvent>,
+) {
+    let mut tick = |entity: Entity, dot: &mut DamageOverTime, transform: &Transform, damage_type| {
+        if let Some(amount) = dot.tick(time.delta()) {
+            damage_events.write(DamageEvent {
+                target: entity,
+                source: dot.source,
+                amount,
+                damage_type,
+                crit: false,
+                sweet_spot: false,
+                position: transform.translation.truncate(),
+                direction: Vec2::ZERO,
+                indirect: true,
+            });
+        }
+        dot.stacks.tick(time.delta())
+    };
+
+    for (entity, mut burning, transform) in burning_query.iter_mut() {
+        if tick(entity, &mut burning.0, transform, DamageType::Fire) {
+            commands.entity(entity).remove::<Burning>();
+        }
+    }
+    for (entity, mut poisoned, transform) in poisoned_query.iter_mut() {
+        if tick(entity, &mut poisoned.0, transform, DamageType::Poison) {
+            commands.entity(entity).remove::<Poisoned>();
+        }
+    }
+}
+
+// Chilled enemies move slower; speed returns to base once the chill wears off
+fn apply_chill(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut enemy_query: Query<(Entity, &mut Enemy, Option<&mut Chilled>)>,
+) {
+    for (entity, mut enemy, chilled) in enemy_query.iter_mut() {
+        let slow = match chilled {
+            Some(mut chilled) => {
+                if chilled.stacks.tick(time.delta()) {
+                    commands.entity(entity).remove::<Chilled>();
+                    0.0
+                } else {
+                    CHILL_SLOW * chilled.stacks.count as f32
+                }
+            }
+            None => 0.0,
+        };
+        let speed = enemy.base_speed * (1.0 - slow).max(0.0);
+        if enemy.speed != speed {
+            enemy.speed = speed;
+        }
+    }
+}
+
+// Wash each affected enemy's material towards its statuses' colors, more with more stacks
+fn tint_affected_enemies(
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    enemy_query: Query<
+        (
+            &Enemy,
+            &MeshMaterial2d<ColorMaterial>,
+            Option<&Burning>,
+            Option<&Poisoned>,
+            Option<&Chilled>,
+        ),
+        (Without<HitFlash>, Without<Dying>),
+    >,
+) {
+    for (enemy, material, burning, poisoned, chilled) in enemy_query.iter() {
+        let statuses = [
+            burning.map(|b| (DamageType::Fire, &b.0.stacks)),
+            poisoned.map(|p| (DamageType::Poison, &p.0.stacks)),
+            chilled.map(|c| (DamageType::Ice, &c.stacks)),
+        ];
+        let color = statuses
+            .into_iter()
+            .flatten()
+            .fold(enemy.base_color, |color, (damage_type, stacks)| {
+                let weight = (0.3 + 0.1 * stacks.count as f32).min(0.7);
+                color.mix(&damage_type.color(), weight)
+            });
+
+        // Only touch the material when the tint changes, so it isn't re-uploaded every frame
+        if materials.get(&material.0).is_none_or(|m| m.color == color) {
+            continue;
+        }
+        if let Some(material) = materials.get_mut(&material.0) {
+            material.color = color;
+        }
+    }
+}
@@ -1,21 +1,58 @@
-use crate::sword::Sword;
+use crate::curve::{lerp, lerp_angle, ArcLengthTable, Easing, Spline, SplineKind};
+use crate::damage::DamageEvent;
+use crate::debug_overlay::SwingTuning;
+use crate::input_map::{Action, ActionState};
+use crate::player::{PlayerHealth, PlayerStats};
+use crate::sword::{Sword, SwordNode};
+use crate::sword_collider::HitRegistry;
+use crate::sword_formation::{thrown_count, SwordFormation};
+use crate::sword_throw::ThrownSword;
+use crate::weapon::Weapon;
 use bevy::prelude::*;
-use nalgebra::{Point2, Vector2};
-use std::f32::consts::PI;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::f32::consts::{FRAC_PI_2, PI};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+// Swing curves are saved here by the swing editor, one file per swing type
+pub const SWING_ASSET_DIR: &str = "assets/swings";
+
+const HITSTOP_TIME: f32 = 0.06; // A landed cut freezes its sword for this long
+const HEAVY_HITSTOP_TIME: f32 = 0.1; // For crits and sweet-spot hits
+const BLADE_SPEED_STEP: f32 = 0.02; // Swing progress the blade's speed is measured over
 
 pub struct SwingAnimationPlugin;
 
 impl Plugin for SwingAnimationPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, (animate_sword_swing, handle_input));
+        app.insert_resource(SwingCurves::load_or_default())
+            .init_resource::<SwingBlending>()
+            .add_event::<SwingStartedEvent>()
+            .add_event::<SwingPhaseChangedEvent>()
+            .add_event::<SwingNotifyEvent>()
+            .add_event::<SwingFinishedEvent>()
+            .add_systems(
+                Update,
+                (
+                    (interrupt_on_player_hit, hitstop_on_hit, apply_attack_speed),
+                    handle_input,
+                    animate_sword_swing,
+                    charge_feedback,
+                )
+                    .chain(),
+            );
     }
 }
 
 #[derive(Component)]
 pub struct SwingAnimation {
-    pub timer: Timer,
+    // Windup pose in the swing curve's space, as authored for a node facing right
     pub start_pos: Vec2,
     pub start_rotation: f32,
+    pub facing: f32, // Node's facing when the swing started; -1.0 mirrors the whole swing
     pub startup_timer: Timer,
     pub end_timer: Timer,
     pub is_swinging: bool,
@@ -23,210 +60,1128 @@ pub struct SwingAnimation {
     // Add phase tracking
     pub current_phase: SwingPhase,
     pub swing_timer: Timer, // For the main swing phase
-    // Store the actual end state of swing phase for recovery
-    pub swing_end_pos: Vec2,
-    pub swing_end_rotation: f32,
+    pub delay_timer: Timer, // Wait before startup when swords swing in sequence
+    // Pose the sword was in when the current phase began; startup and recovery start here
+    pub phase_from: SwingPose,
+    pub blend: Option<PoseBlend>,
+    pub pending_transition: Option<SwingTransition>, // Picked up by animate_sword_swing
+    // Notifies up to this point in the swing have been sent
+    pub notified_until: SwingMoment,
+    pub interrupted: bool,
+    pub pending_finish: bool, // Cancelled outside animate_sword_swing, finish not yet sent
+    // Hold-to-charge: the windup pose is held while the button stays down
+    pub is_charging: bool,
+    pub charge: f32,           // 0.0 = tap swing, 1.0 = fully charged
+    pub full_charge_time: f32, // Seconds of holding at the windup pose to reach full charge
+    pub time_scale: SwingTimeScale,
+    pub reach: f32, // Scale of the swing path for the equipped weapon, 1.0 = as authored
+}
+
+impl SwingAnimation {
+    pub fn damage_multiplier(&self) -> f32 {
+        1.0 + self.charge * 2.0
+    }
+
+    // Scale applied to the swing path; longer weapons and charged swings sweep a wider arc
+    pub fn arc_scale(&self) -> f32 {
+        self.reach * (1.0 + self.charge * 0.5)
+    }
+
+    // Playback rate of the swing phase, a charged swing is released faster
+    pub fn speed_scale(&self) -> f32 {
+        1.0 + self.charge * 0.75
+    }
+
+    // Opacity of the swing trail afterimages
+    pub fn trail_intensity(&self) -> f32 {
+        0.35 + self.charge * 0.65
+    }
+
+    // Where playback is, for placing notifies
+    pub fn moment(&self) -> SwingMoment {
+        SwingMoment {
+            phase: self.current_phase,
+            progress: self.phase_progress(),
+        }
+    }
+
+    // Whether the blade cuts right now: between the curve's hit window notifies, once this
+    // sword's turn in the sequence has come
+    pub fn in_hit_window(&self, curve: &SwingCurve) -> bool {
+        if !self.is_swinging || !self.delay_timer.finished() {
+            return false;
+        }
+        let (open, close) = curve.hit_window();
+        let now = self.moment();
+        open <= now && now <= close
+    }
+
+    // Node-space point of a point in the swing curve's space, mirrored like the rest pose
+    pub fn curve_to_node(&self, local: Vec2) -> Vec2 {
+        let point = self.start_pos + local * self.arc_scale();
+        Vec2::new(point.x * self.facing, point.y)
+    }
+
+    pub fn node_to_curve(&self, point: Vec2) -> Vec2 {
+        (Vec2::new(point.x * self.facing, point.y) - self.start_pos) / self.arc_scale()
+    }
+
+    // Node-space pose the startup phase heads for
+    pub fn windup_pose(&self) -> SwingPose {
+        SwingPose::new(self.curve_to_node(Vec2::ZERO), self.start_rotation * self.facing)
+    }
+
+    // Node-space pose of the swing phase at `progress` (before easing)
+    pub fn swing_pose(&self, curve: &SwingCurve, progress: f32) -> SwingPose {
+        let (offset, rotation) = curve.sample(progress, self.start_rotation);
+        SwingPose::new(self.curve_to_node(offset), rotation * self.facing)
+    }
+
+    // Node-space velocity of the point `distance` along the blade from the sword's origin,
+    // in units per second, as the swing curve has it right now. Zero outside the swing phase.
+    pub fn blade_velocity(&self, curve: &SwingCurve, distance: f32) -> Vec2 {
+        if !self.is_swinging || self.current_phase != SwingPhase::Swing {
+            return Vec2::ZERO;
+        }
+        // Hitstop from an earlier hit doesn't slow the blade down on paper
+        let rate = self.speed_scale() * self.time_scale.playback();
+        let duration = self.swing_timer.duration().as_secs_f32() / rate.max(f32::EPSILON);
+        if duration <= 0.0 {
+            return Vec2::ZERO;
+        }
+        let point = |progress: f32| {
+            let pose = self.swing_pose(curve, progress);
+            pose.position + Vec2::from_angle(pose.rotation).rotate(Vec2::Y * distance)
+        };
+        let to = self.swing_timer.fraction().max(BLADE_SPEED_STEP);
+        let from = to - BLADE_SPEED_STEP;
+        (point(to) - point(from)) / (BLADE_SPEED_STEP * duration)
+    }
+
+    // Progress through the current phase, 0.0 when it begins and 1.0 when it ends
+    pub fn phase_progress(&self) -> f32 {
+        match self.current_phase {
+            SwingPhase::Startup => self.startup_timer.fraction(),
+            SwingPhase::Swing => self.swing_timer.fraction(),
+            SwingPhase::Recovery => self.end_timer.fraction(),
+        }
+    }
+
+    // Abandon the swing; the idle pose eases the sword back to rest from wherever it is
+    pub fn cancel(&mut self) {
+        if self.is_swinging {
+            self.pending_finish = true;
+            self.interrupted = true;
+        }
+        self.is_swinging = false;
+        self.is_charging = false;
+        self.charge = 0.0;
+        self.blend = None;
+        self.pending_transition = None;
+    }
+
+    // Switch phase; the new phase starts from the sword's pose on its first frame
+    pub fn enter_phase(&mut self, phase: SwingPhase, transition: SwingTransition) {
+        match phase {
+            SwingPhase::Startup => self.startup_timer.reset(),
+            SwingPhase::Swing => self.swing_timer.reset(),
+            SwingPhase::Recovery => self.end_timer.reset(),
+        }
+        self.current_phase = phase;
+        self.pending_transition = Some(transition);
+    }
+
+    // Whether `source` may break off this swing right now
+    pub fn can_interrupt(&self, source: SwingInterrupt) -> bool {
+        !self.is_swinging || source.priority() > self.current_phase.commitment()
+    }
+
+    // Break off the swing if `source` outranks the current phase. A swing that is winding up
+    // or cutting returns to rest through its recovery, unless `source` takes the sword over
+    // itself; one already recovering is let go. Returns whether the sword is now free of
+    // the swing's commitment.
+    pub fn interrupt(&mut self, source: SwingInterrupt) -> bool {
+        if !self.can_interrupt(source) {
+            return false;
+        }
+        if !self.is_swinging {
+            return true;
+        }
+        let started = self.delay_timer.finished();
+        let recovering = matches!(self.current_phase, SwingPhase::Recovery);
+        if !started || recovering || !source.recovers() {
+            self.cancel();
+        } else {
+            self.is_charging = false;
+            self.charge = 0.0;
+            self.interrupted = true;
+            // The rest of the swing's notifies never happen
+            self.notified_until = SwingMoment::END;
+            self.enter_phase(SwingPhase::Recovery, SwingTransition::Interrupted);
+        }
+        true
+    }
+}
+
+// Playback rate of a whole swing: delays, every phase, charging and crossfades. Only time is
+// scaled; the path is sampled at the same progress, so its shape never changes, and
+// notifies stay at the same point of their phase.
+#[derive(Clone, Debug)]
+pub struct SwingTimeScale {
+    pub attack_speed: f32, // The player's stat, copied over by apply_attack_speed
+    pub weapon: f32,       // Speed multiplier of the equipped weapon
+    pub hitstop: f32,      // Seconds left frozen, counted down in game time
+}
+
+impl Default for SwingTimeScale {
+    fn default() -> Self {
+        Self {
+            attack_speed: 1.0,
+            weapon: 1.0,
+            hitstop: 0.0,
+        }
+    }
 }
 
-#[derive(Clone)]
+impl SwingTimeScale {
+    pub fn rate(&self) -> f32 {
+        if self.hitstop > 0.0 {
+            0.0
+        } else {
+            self.playback()
+        }
+    }
+
+    // Rate ignoring hitstop
+    pub fn playback(&self) -> f32 {
+        (self.attack_speed * self.weapon).max(0.0)
+    }
+
+    // Swing time that passes during `delta` of game time
+    pub fn advance(&mut self, delta: Duration) -> Duration {
+        if self.hitstop > 0.0 {
+            self.hitstop = (self.hitstop - delta.as_secs_f32()).max(0.0);
+            return Duration::ZERO;
+        }
+        delta.mul_f32(self.rate())
+    }
+}
+
+// Position and rotation of a sword relative to its node
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SwingPose {
+    pub position: Vec2,
+    pub rotation: f32,
+}
+
+impl SwingPose {
+    pub fn new(position: Vec2, rotation: f32) -> Self {
+        Self { position, rotation }
+    }
+
+    pub fn from_transform(transform: &Transform) -> Self {
+        let (_, _, rotation) = transform.rotation.to_euler(EulerRot::XYZ);
+        Self::new(transform.translation.truncate(), rotation)
+    }
+
+    // Rotation turns the short way, so blending never spins the blade through extra turns
+    pub fn lerp(&self, other: SwingPose, t: f32) -> Self {
+        Self::new(
+            self.position.lerp(other.position, t),
+            lerp_angle(self.rotation, other.rotation, t),
+        )
+    }
+
+    pub fn apply(&self, transform: &mut Transform) {
+        transform.translation = self.position.extend(transform.translation.z);
+        transform.rotation = Quat::from_rotation_z(self.rotation);
+    }
+}
+
+// Crossfade from the pose a phase change interrupted
+pub struct PoseBlend {
+    pub from: SwingPose,
+    pub timer: Timer,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum SwingTransition {
+    Startup,     // Rest (or wherever the sword is) into the windup
+    Swing,       // Windup into the cut
+    Recovery,    // End of the cut back towards rest
+    Interrupted, // Windup or cut broken off by a higher-priority action
+}
+
+// Crossfade time per phase transition (seconds), on top of each phase starting from the
+// sword's actual pose. Zero disables the crossfade.
+#[derive(Resource)]
+pub struct SwingBlending {
+    pub startup: f32,
+    pub swing: f32,
+    pub recovery: f32,
+    pub interrupted: f32,
+}
+
+impl Default for SwingBlending {
+    fn default() -> Self {
+        Self {
+            startup: 0.0,
+            swing: 0.04, // Smooths out the charge shake
+            recovery: 0.05,
+            interrupted: 0.1,
+        }
+    }
+}
+
+impl SwingBlending {
+    pub fn crossfade(&self, transition: SwingTransition) -> f32 {
+        match transition {
+            SwingTransition::Startup => self.startup,
+            SwingTransition::Swing => self.swing,
+            SwingTransition::Recovery => self.recovery,
+            SwingTransition::Interrupted => self.interrupted,
+        }
+    }
+}
+
+// Actions that can break off a swing, by priority
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum SwingInterrupt {
+    Dash,
+    Parry,
+    Hit, // The player took damage
+}
+
+impl SwingInterrupt {
+    fn priority(&self) -> u8 {
+        match self {
+            SwingInterrupt::Dash => 1,
+            SwingInterrupt::Parry => 1,
+            SwingInterrupt::Hit => 3,
+        }
+    }
+
+    // Whether the broken-off swing plays a recovery; a parry poses the sword itself
+    fn recovers(&self) -> bool {
+        !matches!(self, SwingInterrupt::Parry)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
 pub enum SwingType {
-    Vertical,
+    Vertical,   // Overhead arc down and through
+    Horizontal, // Flat sweep across the front
+    Thrust,     // Straight jab, blade along the line of travel
 }
 
-#[derive(Clone)]
+impl SwingType {
+    pub const ALL: [SwingType; 3] = [SwingType::Vertical, SwingType::Horizontal, SwingType::Thrust];
+
+    // File name of the swing's asset in SWING_ASSET_DIR
+    pub fn asset_path(&self) -> PathBuf {
+        let name = match self {
+            SwingType::Vertical => "vertical",
+            SwingType::Horizontal => "horizontal",
+            SwingType::Thrust => "thrust",
+        };
+        PathBuf::from(SWING_ASSET_DIR).join(format!("{}.swing.ron", name))
+    }
+
+    // Played at the curve's WHOOSH notify
+    pub fn whoosh_sound(&self) -> &'static str {
+        match self {
+            SwingType::Vertical => "audio/whoosh_vertical.ogg",
+            SwingType::Horizontal => "audio/whoosh_horizontal.ogg",
+            SwingType::Thrust => "audio/whoosh_thrust.ogg",
+        }
+    }
+
+    pub fn next(&self) -> SwingType {
+        let index = SwingType::ALL.iter().position(|t| t == self).unwrap_or(0);
+        SwingType::ALL[(index + 1) % SwingType::ALL.len()]
+    }
+}
+
+// In playback order, so later phases compare greater
+#[derive(Clone, Copy, PartialEq, PartialOrd, Debug, Serialize, Deserialize)]
 pub enum SwingPhase {
     Startup,  // Moving to start position
     Swing,    // Main swing animation
     Recovery, // Moving to end position
 }
 
+impl SwingPhase {
+    // How hard the phase is to interrupt; only a higher SwingInterrupt priority breaks it
+    fn commitment(&self) -> u8 {
+        match self {
+            SwingPhase::Startup | SwingPhase::Recovery => 0,
+            SwingPhase::Swing => 2, // The cut itself only yields to getting hit
+        }
+    }
+}
+
 fn handle_input(
-   mouse: Res<ButtonInput<MouseButton>>,
-   mut sword_query: Query<&mut SwingAnimation, With<Sword>>,
+    actions: Res<ActionState>,
+    node_query: Query<(Entity, &Children, &SwordNode, Option<&SwordFormation>)>,
+    mut sword_query: Query<(&Sword, &mut SwingAnimation, &mut HitRegistry, Option<&mut Weapon>)>,
+    thrown_query: Query<&ThrownSword>,
 ) {
-   if let Ok(mut swing) = sword_query.single_mut() {
-       if !swing.is_swinging {
-           if mouse.just_pressed(MouseButton::Left) {
-               start_swing(&mut swing, SwingType::Vertical);
-           }
-       }
-   }
+    // Letting go of the button lets every charging sword go. Checked on the held state rather
+    // than the release, which is dropped while paused or in the bindings menu.
+    if !actions.pressed(Action::Swing) {
+        for (_, mut swing, _, _) in sword_query.iter_mut() {
+            if swing.is_charging {
+                swing.is_charging = false;
+            }
+        }
+    }
+
+    // A heavy swing skips the hold and releases at full charge
+    let heavy = actions.just_pressed(Action::HeavySwing);
+    if !actions.just_pressed(Action::Swing) && !heavy {
+        return;
+    }
+    // A tap pressed and released within one frame has nothing left to hold
+    let charging = !heavy && actions.pressed(Action::Swing);
+
+    let default_formation = SwordFormation::default();
+    for (node, children, sword_node, formation) in node_query.iter() {
+        let formation = formation.unwrap_or(&default_formation);
+        let attached = children.iter().filter(|c| sword_query.contains(*c)).count();
+        let count = attached + thrown_count(node, &thrown_query);
+
+        // Idle swords on this node, in formation order
+        let mut idle_swords: Vec<(usize, Entity)> = children
+            .iter()
+            .filter_map(|child| {
+                let (sword, swing, _, _) = sword_query.get(child).ok()?;
+                (!swing.is_swinging).then_some((sword.slot, child))
+            })
+            .collect();
+        idle_swords.sort();
+
+        for (order, (slot, sword_entity)) in idle_swords.into_iter().enumerate() {
+            if let Ok((_, mut swing, mut hits, weapon)) = sword_query.get_mut(sword_entity) {
+                hits.clear();
+                // Each blade winds up from its own slot and cuts its own path
+                (swing.start_pos, swing.start_rotation) = formation.windup_pose(slot, count);
+                swing.facing = sword_node.facing;
+                // Each swing plays the next move of the weapon's moveset
+                let swing_type = weapon.map_or(SwingType::Vertical, |mut w| w.next_swing());
+                let delay = formation.swing_delay(order);
+                start_swing(&mut swing, swing_type, delay, charging, heavy);
+            }
+        }
+    }
 }
 
-fn start_swing(swing: &mut SwingAnimation, swing_type: SwingType) {
+fn start_swing(
+    swing: &mut SwingAnimation,
+    swing_type: SwingType,
+    delay: f32,
+    charging: bool,
+    heavy: bool,
+) {
     swing.is_swinging = true;
-    swing.current_phase = SwingPhase::Startup;
-    swing.startup_timer.reset();
+    swing.delay_timer = Timer::from_seconds(delay, TimerMode::Once);
+    swing.is_charging = charging;
+    swing.charge = if heavy { 1.0 } else { 0.0 };
     swing.swing_timer.reset();
     swing.end_timer.reset();
     swing.swing_type = swing_type;
+    swing.interrupted = false;
+    swing.pending_finish = false;
+    swing.notified_until = SwingMoment::START;
+    swing.enter_phase(SwingPhase::Startup, SwingTransition::Startup);
 }
 
-fn animate_sword_swing(
-    time: Res<Time>,
-    mut sword_query: Query<(&mut Transform, &mut SwingAnimation), With<Sword>>,
+// A sword that lands a cut freezes for a moment to sell the impact
+fn hitstop_on_hit(
+    mut damage_events: EventReader<DamageEvent>,
+    mut swing_query: Query<&mut SwingAnimation>,
+) {
+    for event in damage_events.read() {
+        // Burns and arcs land long after the cut, or somewhere else entirely
+        if event.indirect {
+            continue;
+        }
+        let Some(source) = event.source else {
+            continue;
+        };
+        let Ok(mut swing) = swing_query.get_mut(source) else {
+            continue;
+        };
+        if swing.is_swinging {
+            let hitstop = if event.crit || event.sweet_spot {
+                HEAVY_HITSTOP_TIME
+            } else {
+                HITSTOP_TIME
+            };
+            swing.time_scale.hitstop = swing.time_scale.hitstop.max(hitstop);
+        }
+    }
+}
+
+// Every sword swings at the player's attack speed, times any debug override
+fn apply_attack_speed(
+    player_query: Query<&PlayerStats>,
+    tuning: Res<SwingTuning>,
+    mut swing_query: Query<&mut SwingAnimation>,
+) {
+    let stat = player_query.single().map_or(1.0, |stats| stats.attack_speed);
+    let attack_speed = stat * tuning.attack_speed;
+    for mut swing in swing_query.iter_mut() {
+        if swing.time_scale.attack_speed != attack_speed {
+            swing.time_scale.attack_speed = attack_speed;
+        }
+    }
+}
+
+// Getting hit knocks every sword out of its swing
+fn interrupt_on_player_hit(
+    mut damage_events: EventReader<DamageEvent>,
+    player_query: Query<(), With<PlayerHealth>>,
+    mut swing_query: Query<&mut SwingAnimation>,
 ) {
-    for (mut transform, mut swing) in sword_query.iter_mut() {
+    let hits = damage_events.read().filter(|event| player_query.contains(event.target));
+    if hits.count() == 0 {
+        return;
+    }
+    for mut swing in swing_query.iter_mut() {
         if swing.is_swinging {
-            match swing.current_phase {
-                SwingPhase::Startup => {
-                    // Phase 1: Move to start position using linear interpolation
-                    swing.startup_timer.tick(time.delta());
-                    let startup_progress = swing.startup_timer.elapsed_secs() / swing.startup_timer.duration().as_secs_f32();
-                    
-                    if startup_progress >= 1.0 {
-                        // Move to swing phase
-                        swing.current_phase = SwingPhase::Swing;
-                        swing.swing_timer.reset();
-                        
-                        // Set to exact start position
-                        transform.translation = Vec3::new(swing.start_pos.x, swing.start_pos.y, 0.0);
-                        transform.rotation = Quat::from_rotation_z(swing.start_rotation);
-                    } else {
-                        // Linear interpolation to start position
-                        let current_pos = Vec2::lerp(Vec2::ZERO, swing.start_pos, startup_progress);
-                        let current_rotation = lerp(0.0, swing.start_rotation, startup_progress);
-                        
-                        transform.translation = Vec3::new(current_pos.x, current_pos.y, 0.0);
-                        transform.rotation = Quat::from_rotation_z(current_rotation);
-                    }
+            swing.interrupt(SwingInterrupt::Hit);
+        }
+    }
+}
+
+// Samples used to measure a swing path for constant-speed playback
+const ARC_LENGTH_SAMPLES: usize = 64;
+
+// Notifies bounding the part of a swing in which the blade deals damage
+pub const HIT_WINDOW_OPEN: &str = "hit_window_open";
+pub const HIT_WINDOW_CLOSE: &str = "hit_window_close";
+pub const WHOOSH: &str = "whoosh"; // Plays the swing type's whoosh sound
+
+// A point in a swing: a phase and the progress through it. Pinned to its phase, it stays in
+// step with the swing however tuning, weapon speed or charge stretch the phases.
+#[derive(Clone, Copy, PartialEq, PartialOrd, Debug, Serialize, Deserialize)]
+pub struct SwingMoment {
+    pub phase: SwingPhase,
+    pub progress: f32, // 0.0 = the phase begins, 1.0 = it ends
+}
+
+impl SwingMoment {
+    // Before anything, so notifies at the very start fire too
+    pub const START: SwingMoment = SwingMoment::new(SwingPhase::Startup, -1.0);
+    // Past everything, once the swing is over
+    pub const END: SwingMoment = SwingMoment::new(SwingPhase::Recovery, f32::INFINITY);
+
+    pub const fn new(phase: SwingPhase, progress: f32) -> Self {
+        Self { phase, progress }
+    }
+}
+
+// A named moment in a swing, sent as a SwingNotifyEvent when playback passes it
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(try_from = "SwingNotifyFile")]
+pub struct SwingNotify {
+    pub name: String,
+    pub at: SwingMoment,
+}
+
+impl SwingNotify {
+    pub fn new(name: &str, phase: SwingPhase, progress: f32) -> Self {
+        Self {
+            name: name.to_string(),
+            at: SwingMoment::new(phase, progress),
+        }
+    }
+}
+
+// A SwingNotify as saved. Notifies saved before they were pinned to phases have a `time`
+// over the whole swing instead, placed here as it fell with the default phase timings.
+#[derive(Deserialize)]
+struct SwingNotifyFile {
+    name: String,
+    #[serde(default, deserialize_with = "deserialize_some")]
+    at: Option<SwingMoment>,
+    #[serde(default, deserialize_with = "deserialize_some")]
+    time: Option<f32>,
+}
+
+impl TryFrom<SwingNotifyFile> for SwingNotify {
+    type Error = String;
+
+    fn try_from(file: SwingNotifyFile) -> Result<Self, Self::Error> {
+        let at = match (file.at, file.time) {
+            (Some(at), _) => at,
+            (None, Some(time)) => {
+                // Startup, swing and recovery of 0.1s, 0.4s and 0.2s
+                let (startup_end, swing_end) = (0.1 / 0.7, 0.5 / 0.7);
+                if time < startup_end {
+                    SwingMoment::new(SwingPhase::Startup, time / startup_end)
+                } else if time < swing_end {
+                    let progress = (time - startup_end) / (swing_end - startup_end);
+                    SwingMoment::new(SwingPhase::Swing, progress)
+                } else {
+                    let progress = (time - swing_end) / (1.0 - swing_end);
+                    SwingMoment::new(SwingPhase::Recovery, progress)
+                }
+            }
+            (None, None) => return Err(format!("notify `{}` has no time", file.name)),
+        };
+        Ok(SwingNotify { name: file.name, at })
+    }
+}
+
+// Blade rotation added on top of the windup rotation at an eased swing progress
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct RotationKey {
+    pub time: f32,  // 0.0 = start of the swing phase, 1.0 = end
+    pub angle: f32, // Radians; keys may wind through several turns
+    #[serde(default)]
+    pub easing: Easing, // Towards the next key
+}
+
+impl RotationKey {
+    pub fn new(time: f32, angle: f32) -> Self {
+        Self {
+            time,
+            angle,
+            easing: Easing::Linear,
+        }
+    }
+}
+
+// Where the blade points during the swing phase
+#[derive(Clone, Copy, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub enum BladeOrientation {
+    #[default]
+    RotationKeys,
+    // Blade tip along the path's direction of travel, turned by `offset` (radians)
+    FollowPath { offset: f32 },
+}
+
+// Path of a swing's main phase, relative to the windup pose
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(try_from = "SwingCurveFile")]
+pub struct SwingCurve {
+    path: Spline, // The first point is the windup pose itself; edited through set_point
+    #[serde(skip)]
+    arc_lengths: ArcLengthTable, // Of `path`, rebuilt whenever it changes
+    pub easing: Easing,
+    #[serde(default)]
+    pub constant_speed: bool, // Eased progress is distance along the path, not spline parameter
+    #[serde(default)]
+    pub orientation: BladeOrientation,
+    pub rotation_keys: Vec<RotationKey>, // Sorted by time
+    #[serde(default)]
+    pub notifies: Vec<SwingNotify>,
+}
+
+// A SwingCurve as saved. Curves saved before swings had splines have a single cubic bezier
+// in `control_points` instead of a `path`.
+#[derive(Deserialize)]
+struct SwingCurveFile {
+    #[serde(default, deserialize_with = "deserialize_some")]
+    path: Option<Spline>,
+    #[serde(default, deserialize_with = "deserialize_some")]
+    control_points: Option<[Vec2; 4]>,
+    easing: Easing,
+    #[serde(default)]
+    constant_speed: bool,
+    #[serde(default)]
+    orientation: BladeOrientation,
+    rotation_keys: Vec<RotationKey>,
+    #[serde(default)]
+    notifies: Vec<SwingNotify>,
+}
+
+// Lets an Option field be written without Some(..), and missing fields fall back to None
+fn deserialize_some<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    T::deserialize(deserializer).map(Some)
+}
+
+impl TryFrom<SwingCurveFile> for SwingCurve {
+    type Error = String;
+
+    fn try_from(file: SwingCurveFile) -> Result<Self, Self::Error> {
+        let path = match (file.path, file.control_points) {
+            (Some(path), _) => path,
+            (None, Some(points)) => Spline::new(SplineKind::Bezier, points.to_vec()),
+            (None, None) => return Err("swing curve has no path".to_string()),
+        };
+        let mut curve = SwingCurve {
+            path,
+            arc_lengths: ArcLengthTable::default(),
+            easing: file.easing,
+            constant_speed: file.constant_speed,
+            orientation: file.orientation,
+            rotation_keys: file.rotation_keys,
+            notifies: file.notifies,
+        };
+        curve.remeasure();
+        Ok(curve)
+    }
+}
+
+impl SwingCurve {
+    // Rebuild the arc length table after the path changed
+    fn remeasure(&mut self) {
+        self.arc_lengths = ArcLengthTable::new(&self.path, ARC_LENGTH_SAMPLES);
+    }
+
+    pub fn path(&self) -> &Spline {
+        &self.path
+    }
+
+    // Move a raw control point; see Spline::set_handle for moving it where editors show it
+    pub fn set_point(&mut self, index: usize, point: Vec2) {
+        if let Some(existing) = self.path.points.get_mut(index) {
+            *existing = point;
+            self.remeasure();
+        }
+    }
+
+    pub fn set_handle(&mut self, index: usize, position: Vec2) {
+        self.path.set_handle(index, position);
+        self.remeasure();
+    }
+
+    // Offset from the windup position and blade rotation at swing progress `t`, for a swing
+    // whose windup pose has the given rotation
+    pub fn sample(&self, t: f32, start_rotation: f32) -> (Vec2, f32) {
+        let progress = self.easing.apply(t);
+        let rotation = match self.orientation {
+            BladeOrientation::RotationKeys => start_rotation + self.rotation_at(progress),
+            BladeOrientation::FollowPath { offset } => {
+                let tangent = self.path.tangent(self.path_parameter(progress));
+                if tangent == Vec2::ZERO {
+                    start_rotation + offset
+                } else {
+                    // The blade's tip is its local +Y
+                    tangent.to_angle() - FRAC_PI_2 + offset
                 }
+            }
+        };
+        (self.position_at(progress), rotation)
+    }
+
+    // Point on the path at an already eased progress
+    pub fn position_at(&self, progress: f32) -> Vec2 {
+        self.path.position(self.path_parameter(progress))
+    }
+
+    fn path_parameter(&self, progress: f32) -> f32 {
+        if self.constant_speed {
+            self.arc_lengths.parameter(progress)
+        } else {
+            progress
+        }
+    }
+
+    // Eased between keys by each key's easing, held flat before the first and after the last
+    pub fn rotation_at(&self, progress: f32) -> f32 {
+        let keys = &self.rotation_keys;
+        let (Some(first), Some(last)) = (keys.first(), keys.last()) else {
+            return 0.0;
+        };
+        if progress <= first.time {
+            return first.angle;
+        }
+        for pair in keys.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            if progress <= b.time {
+                let span = (b.time - a.time).max(f32::EPSILON);
+                let t = a.easing.apply((progress - a.time) / span);
+                return lerp(a.angle, b.angle, t);
+            }
+        }
+        last.angle
+    }
+
+    // Where the hit window notifies sit; without them the blade cuts for the whole swing phase
+    pub fn hit_window(&self) -> (SwingMoment, SwingMoment) {
+        let find = |name: &str| self.notifies.iter().find(|n| n.name == name).map(|n| n.at);
+        (
+            find(HIT_WINDOW_OPEN).unwrap_or(SwingMoment::new(SwingPhase::Swing, 0.0)),
+            find(HIT_WINDOW_CLOSE).unwrap_or(SwingMoment::new(SwingPhase::Swing, 1.0)),
+        )
+    }
+
+    // Keep keys in time order after one has been moved
+    pub fn sort_rotation_keys(&mut self) {
+        self.rotation_keys.sort_by(|a, b| a.time.total_cmp(&b.time));
+    }
+}
+
+// Swing paths per swing type; a resource so they can be tuned at runtime
+#[derive(Resource)]
+pub struct SwingCurves {
+    pub vertical: SwingCurve,
+    pub horizontal: SwingCurve,
+    pub thrust: SwingCurve,
+}
+
+impl Default for SwingCurves {
+    fn default() -> Self {
+        let mut curves = Self {
+            // A pronounced U-shaped arc
+            vertical: SwingCurve {
+                path: Spline::new(
+                    SplineKind::Bezier,
+                    vec![
+                        Vec2::new(0.0, 0.0),
+                        Vec2::new(0.0, -200.0),
+                        Vec2::new(200.0, -200.0),
+                        Vec2::new(200.0, 0.0),
+                    ],
+                ),
+                arc_lengths: ArcLengthTable::default(), // Built below
+                easing: Easing::SmoothStep,
+                constant_speed: false,
+                orientation: BladeOrientation::RotationKeys,
+                rotation_keys: vec![RotationKey::new(0.0, 0.0), RotationKey::new(1.0, PI * 2.1)],
+                notifies: vec![
+                    SwingNotify::new(HIT_WINDOW_OPEN, SwingPhase::Swing, 0.0),
+                    SwingNotify::new(WHOOSH, SwingPhase::Swing, 0.3),
+                    SwingNotify::new(HIT_WINDOW_CLOSE, SwingPhase::Swing, 1.0),
+                    SwingNotify::new("cancel_allowed", SwingPhase::Recovery, 0.2),
+                ],
+            },
+            // A wide, shallow sweep that levels the blade out as it crosses
+            horizontal: SwingCurve {
+                path: Spline::new(
+                    SplineKind::CatmullRom,
+                    vec![
+                        Vec2::new(0.0, 0.0),
+                        Vec2::new(70.0, -70.0),
+                        Vec2::new(170.0, -80.0),
+                        Vec2::new(240.0, -30.0),
+                    ],
+                ),
+                arc_lengths: ArcLengthTable::default(), // Built below
+                easing: Easing::CubicInOut,
+                constant_speed: true,
+                orientation: BladeOrientation::RotationKeys,
+                rotation_keys: vec![RotationKey::new(0.0, 0.0), RotationKey::new(1.0, -PI * 1.2)],
+                // The eased ends barely move, so they don't cut
+                notifies: vec![
+                    SwingNotify::new(HIT_WINDOW_OPEN, SwingPhase::Swing, 0.05),
+                    SwingNotify::new(WHOOSH, SwingPhase::Swing, 0.25),
+                    SwingNotify::new(HIT_WINDOW_CLOSE, SwingPhase::Swing, 0.95),
+                    SwingNotify::new("cancel_allowed", SwingPhase::Recovery, 0.2),
+                ],
+            },
+            // Pulled back a touch, then driven straight out tip first
+            thrust: SwingCurve {
+                path: Spline::new(
+                    SplineKind::Bezier,
+                    vec![
+                        Vec2::new(0.0, 0.0),
+                        Vec2::new(-20.0, -15.0),
+                        Vec2::new(90.0, -50.0),
+                        Vec2::new(200.0, -60.0),
+                    ],
+                ),
+                arc_lengths: ArcLengthTable::default(), // Built below
+                easing: Easing::ExpoOut,
+                constant_speed: false,
+                orientation: BladeOrientation::FollowPath { offset: 0.0 },
+                rotation_keys: vec![RotationKey::new(0.0, 0.0), RotationKey::new(1.0, 0.0)],
+                // The expo-out jab is fully extended well before the phase ends
+                notifies: vec![
+                    SwingNotify::new(HIT_WINDOW_OPEN, SwingPhase::Swing, 0.0),
+                    SwingNotify::new(WHOOSH, SwingPhase::Swing, 0.1),
+                    SwingNotify::new(HIT_WINDOW_CLOSE, SwingPhase::Swing, 0.8),
+                    SwingNotify::new("cancel_allowed", SwingPhase::Recovery, 0.0),
+                ],
+            },
+        };
+        for swing_type in SwingType::ALL {
+            curves.get_mut(swing_type).remeasure();
+        }
+        curves
+    }
+}
+
+impl SwingCurves {
+    pub fn get(&self, swing_type: SwingType) -> &SwingCurve {
+        match swing_type {
+            SwingType::Vertical => &self.vertical,
+            SwingType::Horizontal => &self.horizontal,
+            SwingType::Thrust => &self.thrust,
+        }
+    }
+
+    pub fn get_mut(&mut self, swing_type: SwingType) -> &mut SwingCurve {
+        match swing_type {
+            SwingType::Vertical => &mut self.vertical,
+            SwingType::Horizontal => &mut self.horizontal,
+            SwingType::Thrust => &mut self.thrust,
+        }
+    }
+
+    // Built-in curves, overridden by any swing asset saved from the editor
+    pub fn load_or_default() -> Self {
+        let mut curves = SwingCurves::default();
+        for swing_type in SwingType::ALL {
+            let path = swing_type.asset_path();
+            if !path.exists() {
+                continue;
+            }
+            match SwingCurve::load(&path) {
+                Ok(curve) => *curves.get_mut(swing_type) = curve,
+                Err(error) => {
+                    let path = path.display();
+                    warn!(target: "swing", "Failed to load swing asset {}: {}", path, error);
+                }
+            }
+        }
+        curves
+    }
+}
+
+impl SwingCurve {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        ron::from_str(&text).map_err(|e| e.to_string())
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let text = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|e| e.to_string())?;
+        fs::write(path, text).map_err(|e| e.to_string())
+    }
+}
+
+// Sent when a sword leaves its startup phase and the blade starts moving
+#[derive(Event)]
+pub struct SwingStartedEvent {
+    pub sword: Entity,
+    pub swing_type: SwingType,
+    pub charge: f32,
+    pub speed: f32, // Playback rate of the swing phase
+}
+
+// Sent on the first frame of each phase, once any sequence delay has passed
+#[derive(Event)]
+pub struct SwingPhaseChangedEvent {
+    pub sword: Entity,
+    pub phase: SwingPhase,
+}
+
+// A SwingNotify of the sword's swing curve was reached
+#[derive(Event)]
+pub struct SwingNotifyEvent {
+    pub sword: Entity,
+    pub swing_type: SwingType,
+    pub name: String,
+}
+
+// The sword is back to idle, either after its recovery or because the swing was broken off
+#[derive(Event)]
+pub struct SwingFinishedEvent {
+    pub sword: Entity,
+    pub interrupted: bool,
+}
+
+// How quickly an idle sword settles into its rest pose (per second)
+const REST_FOLLOW_SPEED: f32 = 12.0;
+
+pub fn animate_sword_swing(
+    time: Res<Time>,
+    curves: Res<SwingCurves>,
+    blending: Res<SwingBlending>,
+    // Thrown swords are driven by physics; their phase machine is suspended until caught
+    mut sword_query: Query<
+        (Entity, &mut Transform, &mut SwingAnimation, &Sword),
+        Without<ThrownSword>,
+    >,
+    mut swing_events: EventWriter<SwingStartedEvent>,
+    mut phase_events: EventWriter<SwingPhaseChangedEvent>,
+    mut notify_events: EventWriter<SwingNotifyEvent>,
+    mut finished_events: EventWriter<SwingFinishedEvent>,
+) {
+    for (entity, mut transform, mut swing, sword) in sword_query.iter_mut() {
+        let (rest_pos, rest_rotation) = sword.rest_pose();
+        let rest = SwingPose::new(rest_pos, rest_rotation);
+
+        if swing.pending_finish {
+            swing.pending_finish = false;
+            finished_events.write(SwingFinishedEvent {
+                sword: entity,
+                interrupted: true,
+            });
+        }
+
+        if !swing.is_swinging {
+            // Idle: ease towards the rest pose so runtime offset changes don't pop
+            let t = (REST_FOLLOW_SPEED * time.delta_secs()).min(1.0);
+            let current_pos = transform.translation.truncate().lerp(rest_pos, t);
+            transform.translation = Vec3::new(current_pos.x, current_pos.y, transform.translation.z);
+            transform.rotation = transform.rotation.slerp(Quat::from_rotation_z(rest_rotation), t);
+            continue;
+        }
+
+        // Attack speed, weapon speed and hitstop stretch everything below alike
+        let delta = swing.time_scale.advance(time.delta());
+
+        // Hold at rest until this sword's turn in the sequence comes up
+        swing.delay_timer.tick(delta);
+        if !swing.delay_timer.finished() {
+            continue;
+        }
+
+        // A phase just began: it starts from wherever the sword actually is
+        if let Some(transition) = swing.pending_transition.take() {
+            let current = SwingPose::from_transform(&transform);
+            swing.phase_from = current;
+            let crossfade = blending.crossfade(transition);
+            swing.blend = (crossfade > 0.0).then(|| PoseBlend {
+                from: current,
+                timer: Timer::from_seconds(crossfade, TimerMode::Once),
+            });
+            phase_events.write(SwingPhaseChangedEvent {
+                sword: entity,
+                phase: swing.current_phase,
+            });
+        }
+
+        let windup = swing.windup_pose();
+        let pose = match swing.current_phase {
+            SwingPhase::Startup => {
+                // Phase 1: Move to start position using linear interpolation
+                swing.startup_timer.tick(delta);
+                let startup_progress = swing.startup_timer.elapsed_secs() / swing.startup_timer.duration().as_secs_f32();
                 
-                SwingPhase::Swing => {
-                    // Phase 2: Main swing using cubic bezier
-                    swing.swing_timer.tick(time.delta());
-                    let swing_progress = swing.swing_timer.elapsed_secs() / swing.swing_timer.duration().as_secs_f32();
-                    
-                    if swing_progress >= 1.0 {
-                        // Store the final swing position and rotation for recovery phase
-                        let (swing_position, swing_rotation) = match swing.swing_type {
-                            SwingType::Vertical => calculate_vertical_swing_cubic(1.0),
-                        };
-                        swing.swing_end_pos = swing.start_pos + swing_position;
-                        swing.swing_end_rotation = swing.start_rotation + swing_rotation;
-                        // Move to recovery phase
-                        swing.current_phase = SwingPhase::Recovery;
-                        swing.end_timer.reset();
-                    } else {
-                        // Use cubic bezier for swing animation
-                        let (swing_position, swing_rotation) = match swing.swing_type {
-                            SwingType::Vertical => calculate_vertical_swing_cubic(swing_progress),
-                        };
-                        
-                        // Add swing motion to start position
-                        let final_pos = swing.start_pos + swing_position;
-                        let final_rotation = swing.start_rotation + swing_rotation;
-                        
-                        transform.translation = Vec3::new(final_pos.x, final_pos.y, 0.0);
-                        transform.rotation = Quat::from_rotation_z(final_rotation);
-                    }
+                if startup_progress >= 1.0 && swing.is_charging {
+                    // Hold the windup pose and build charge until the button is released
+                    swing.charge = (swing.charge + delta.as_secs_f32() / swing.full_charge_time).min(1.0);
+
+                    // Shake harder the longer the charge is held
+                    let shake = rand::thread_rng().gen_range(-1.0..1.0) * swing.charge * 3.0;
+                    SwingPose::new(windup.position + Vec2::new(shake, -shake), windup.rotation)
+                } else if startup_progress >= 1.0 {
+                    // Move to swing phase
+                    swing.enter_phase(SwingPhase::Swing, SwingTransition::Swing);
+                    swing_events.write(SwingStartedEvent {
+                        sword: entity,
+                        swing_type: swing.swing_type,
+                        charge: swing.charge,
+                        speed: swing.speed_scale(),
+                    });
+                    debug!(target: "swing", sword = ?entity, charge = swing.charge, "started");
+                    windup
+                } else {
+                    // From the pose the sword was in when the swing began, not from rest
+                    swing.phase_from.lerp(windup, startup_progress)
                 }
+            }
+            
+            SwingPhase::Swing => {
+                // Phase 2: Main swing along the swing curve, played faster when charged
+                let scaled_delta = delta.mul_f32(swing.speed_scale());
+                swing.swing_timer.tick(scaled_delta);
+                let swing_progress = swing.swing_timer.elapsed_secs() / swing.swing_timer.duration().as_secs_f32();
+                
+                // Ends exactly on the curve's last point; recovery picks up from there
+                let pose = swing.swing_pose(curves.get(swing.swing_type), swing_progress.min(1.0));
+                if swing_progress >= 1.0 {
+                    swing.enter_phase(SwingPhase::Recovery, SwingTransition::Recovery);
+                }
+                pose
+            }
+            
+            SwingPhase::Recovery => {
+                // Phase 3: Move back to the rest pose using linear interpolation
+                swing.end_timer.tick(delta);
+                let end_progress = swing.end_timer.elapsed_secs() / swing.end_timer.duration().as_secs_f32();
                 
-                SwingPhase::Recovery => {
-                    // Phase 3: Move back to origin (0, 0) using linear interpolation
-                    swing.end_timer.tick(time.delta());
-                    let end_progress = swing.end_timer.elapsed_secs() / swing.end_timer.duration().as_secs_f32();
-                    
-                    if end_progress >= 1.0 {
-                        // Animation complete - return to origin
-                        swing.is_swinging = false;
-                        transform.translation = Vec3::ZERO;
-                        transform.rotation = Quat::IDENTITY;
-                    } else {
-                        // Linear interpolation from actual swing end position back to origin
-                        let current_pos = Vec2::lerp(swing.swing_end_pos, Vec2::ZERO, end_progress);
-                        let current_rotation = lerp(swing.swing_end_rotation, 0.0, end_progress);
-                        
-                        transform.translation = Vec3::new(current_pos.x, current_pos.y, 0.0);
-                        transform.rotation = Quat::from_rotation_z(current_rotation);
-                    }
+                if end_progress >= 1.0 {
+                    // Animation complete - settle at the rest pose
+                    swing.is_swinging = false;
+                    swing.charge = 0.0;
+                    finished_events.write(SwingFinishedEvent {
+                        sword: entity,
+                        interrupted: swing.interrupted,
+                    });
+                    rest
+                } else {
+                    // From where the swing ended or was interrupted, turning the short way
+                    // rather than unwinding every turn the swing made
+                    swing.phase_from.lerp(rest, end_progress)
                 }
             }
+        };
+
+        // Crossfade out of the pose the sword was in when the phase changed
+        let pose = match &mut swing.blend {
+            Some(blend) => {
+                blend.timer.tick(delta);
+                let weight = Easing::SmoothStep.apply(blend.timer.fraction());
+                let blended = blend.from.lerp(pose, weight);
+                if blend.timer.finished() {
+                    swing.blend = None;
+                }
+                blended
+            }
+            None => pose,
+        };
+        pose.apply(&mut transform);
+
+        // Send every notify passed since last frame; a finished swing has passed them all
+        let now = if swing.is_swinging {
+            swing.moment()
+        } else {
+            SwingMoment::END
+        };
+        let curve = curves.get(swing.swing_type);
+        for notify in &curve.notifies {
+            if notify.at > swing.notified_until && notify.at <= now {
+                trace!(target: "swing", sword = ?entity, notify = %notify.name, "notify");
+                notify_events.write(SwingNotifyEvent {
+                    sword: entity,
+                    swing_type: swing.swing_type,
+                    name: notify.name.clone(),
+                });
+            }
+        }
+        if now > swing.notified_until {
+            swing.notified_until = now;
         }
     }
 }
 
-fn get_swing_end_position(swing_type: SwingType) -> Vec2 {
-    match swing_type {
-        SwingType::Vertical => Vec2::new(0.0, -80.0),
-    }
-}
-
-fn get_swing_end_rotation(swing_type: SwingType) -> f32 {
-    match swing_type {
-        SwingType::Vertical => PI * 0.9,
-    }
-}
-
-// Keep the original calculate_vertical_swing for reference
-fn calculate_vertical_swing_cubic(t: f32) -> (Vec2, f32) {
-    // Vertical swing using cubic bezier curve
-    let progress = smooth_step(t);
-    
-    // Define cubic bezier control points for a pronounced U-shaped arc
-    let p0 = Point2::new(0.0, 0.0);      // Start relative to start_pos
-    let p1 = Point2::new(00.0, -200.0);  // First control point (far left, slightly down)
-    let p2 = Point2::new(200.0, -200.0);   // Second control point (far right, slightly down)
-    let p3 = Point2::new(200.0, 000.0);    // End relative to start_pos
-    
-    // Calculate position using cubic bezier
-    let pos = cubic_bezier(p0, p1, p2, p3, progress);
-    let position = Vec2::new(pos.x, pos.y);
-    
-    // Rotation follows the swing direction
-    let rotation = lerp(0.0, PI * 2.1, progress);
-    
-    (position, rotation)
-}
-
-// Fixed utility functions using nalgebra
-fn quadratic_bezier(p0: Point2<f32>, p1: Point2<f32>, p2: Point2<f32>, t: f32) -> Point2<f32> {
-    let u = 1.0 - t;
-    let tt = t * t;
-    let uu = u * u;
-    // Convert to vectors for arithmetic, then back to point
-    let v0 = p0.coords;
-    let v1 = p1.coords;
-    let v2 = p2.coords;
-    Point2::from(v0 * uu + v1 * (2.0 * u * t) + v2 * tt)
-}
-
-fn cubic_bezier(
-    p0: Point2<f32>,
-    p1: Point2<f32>,
-    p2: Point2<f32>,
-    p3: Point2<f32>,
-    t: f32,
-) -> Point2<f32> {
-    // Standard cubic bezier formula: B(t) = (1-t)³P₀ + 3(1-t)²tP₁ + 3(1-t)t²P₂ + t³P₃
-    let u = 1.0 - t;
-    let tt = t * t;
-    let uu = u * u;
-    let uuu = uu * u;
-    let ttt = tt * t;
-    
-    // Convert to vectors for arithmetic, then back to point
-    let v0 = p0.coords;
-    let v1 = p1.coords;
-    let v2 = p2.coords;
-    let v3 = p3.coords;
-    
-    let result = v0 * uuu + v1 * (3.0 * uu * t) + v2 * (3.0 * u * tt) + v3 * ttt;
-    Point2::from(result)
-}
-
-fn lerp(a: f32, b: f32, t: f32) -> f32 {
-    a + (b - a) * t
-}
-
-fn smooth_step(t: f32) -> f32 {
-    t * t * (3.0 - 2.0 * t)
-}
-
-fn ease_in_out_cubic(t: f32) -> f32 {
-    if t < 0.5 {
-        4.0 * t * t * t
-    } else {
-        1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
-    }
-}
\ No newline at end of file
+// Tint the blade towards gold while charging, pulsing once fully charged. The color is only
+// touched while charging and put back once afterwards, so other tints aren't painted over.
+fn charge_feedback(
+    time: Res<Time>,
+    mut sword_query: Query<(Entity, &SwingAnimation, &mut Sprite)>,
+    mut base_colors: Local<HashMap<Entity, Color>>, // Color each charging blade started with
+) {
+    for (entity, swing, mut sprite) in sword_query.iter_mut() {
+        if !swing.is_swinging || !swing.is_charging {
+            if let Some(color) = base_colors.remove(&entity) {
+                sprite.color = color;
+            }
+            continue;
+        }
+        base_colors.entry(entity).or_insert(sprite.color);
+        let glow = if swing.charge >= 1.0 {
+            0.75 + 0.25 * (time.elapsed_secs() * 20.0).sin()
+        } else {
+            swing.charge
+        };
+        sprite.color = Color::srgb(1.0, 1.0 - glow * 0.3, 1.0 - glow * 0.8);
+    }
+    // Swords despawned mid-charge
+    base_colors.retain(|entity, _| sword_query.contains(*entity));
+}
@@ -0,0 +1,770 @@
+use crate::curve::{lerp_angle, SplineKind};
+use crate::debug_overlay::SwingTuning;
+use crate::input_map::{Action, ActionState};
+use crate::swing_animation::{
+    animate_sword_swing, BladeOrientation, RotationKey, SwingAnimation, SwingCurve, SwingCurves,
+    SwingType,
+};
+use crate::sword::{Sword, SwordNode};
+use crate::sword_throw::ThrownSword;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use std::f32::consts::{FRAC_PI_2, TAU};
+
+const PATH_SAMPLES: usize = 32;
+const GRAB_RADIUS: f32 = 10.0; // World units around a control point that start a drag
+const SCRUB_SPEED: f32 = 0.5; // Timeline fraction per second while an arrow key is held
+const KEY_TIME_STEP: f32 = 0.05;
+const KEY_ANGLE_STEP: f32 = TAU / 36.0; // 10 degrees
+
+pub struct SwingEditorPlugin;
+
+impl Plugin for SwingEditorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SwingEditor>()
+            .init_resource::<SwingPreview>()
+            .add_systems(Startup, spawn_timeline)
+            .add_systems(
+                Update,
+                (
+                    toggle_swing_editor,
+                    (
+                        handle_editor_buttons,
+                        scrub_timeline,
+                        drag_control_points,
+                        preview_swing_pose.after(animate_sword_swing),
+                        update_timeline,
+                        draw_editor_gizmos,
+                    )
+                        .chain()
+                        .run_if(swing_editor_open),
+                    refresh_editor_panel,
+                )
+                    .chain(),
+            );
+    }
+}
+
+// Swing curve editor: pauses the game and poses the first sword anywhere along its swing
+#[derive(Resource)]
+pub struct SwingEditor {
+    pub open: bool,
+    pub swing_type: SwingType,
+    selected_key: usize, // Index into the curve's rotation keys
+    status: String,      // Result of the last save or revert
+    was_paused: bool,    // Pause state to restore on close
+}
+
+impl Default for SwingEditor {
+    fn default() -> Self {
+        Self {
+            open: false,
+            swing_type: SwingType::Vertical,
+            selected_key: 0,
+            status: String::new(),
+            was_paused: false,
+        }
+    }
+}
+
+pub fn swing_editor_open(editor: Res<SwingEditor>) -> bool {
+    editor.open
+}
+
+pub fn swing_editor_closed(editor: Res<SwingEditor>) -> bool {
+    !editor.open
+}
+
+// Kept apart from SwingEditor so scrubbing and dragging don't rebuild the panel every frame
+#[derive(Resource, Default)]
+struct SwingPreview {
+    time: f32,               // 0.0 = start of startup, 1.0 = end of recovery
+    dragging: Option<usize>, // Control point being dragged
+}
+
+#[derive(Component)]
+struct EditorPanel;
+
+#[derive(Component)]
+struct Timeline;
+
+#[derive(Component)]
+struct TimelineBar;
+
+#[derive(Component)]
+struct TimelineSegment(usize); // 0 = startup, 1 = swing, 2 = recovery
+
+#[derive(Component)]
+struct TimelineMarker;
+
+#[derive(Component)]
+struct TimelineLabel;
+
+#[derive(Component, Clone, Copy)]
+enum EditorButton {
+    CycleSwingType,
+    CycleEasing,
+    ToggleConstantSpeed,
+    ToggleOrientation,
+    TangentOffset(f32),
+    CycleKeyEasing,
+    SelectKey(isize), // Step through the keys by this much
+    KeyTime(f32),
+    KeyAngle(f32),
+    AddKey,
+    RemoveKey,
+    Save,
+    Revert,
+}
+
+fn toggle_swing_editor(
+    actions: Res<ActionState>,
+    mut editor: ResMut<SwingEditor>,
+    mut preview: ResMut<SwingPreview>,
+    mut time: ResMut<Time<Virtual>>,
+    mut timeline_query: Query<&mut Visibility, With<Timeline>>,
+) {
+    if !actions.just_pressed(Action::ToggleSwingEditor) {
+        return;
+    }
+    editor.open = !editor.open;
+    if editor.open {
+        editor.was_paused = time.is_paused();
+        editor.status.clear();
+        time.pause();
+    } else if !editor.was_paused {
+        time.unpause();
+    }
+    preview.dragging = None;
+
+    for mut visibility in timeline_query.iter_mut() {
+        *visibility = if editor.open {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
+
+fn handle_editor_buttons(
+    button_query: Query<(&Interaction, &EditorButton), Changed<Interaction>>,
+    mut editor: ResMut<SwingEditor>,
+    mut curves: ResMut<SwingCurves>,
+) {
+    for (interaction, button) in button_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let swing_type = editor.swing_type;
+        let curve = curves.get_mut(swing_type);
+        if curve.rotation_keys.is_empty() {
+            // Only possible with a hand-edited asset
+            curve.rotation_keys.push(RotationKey::new(0.0, 0.0));
+        }
+        let selected = editor.selected_key.min(curve.rotation_keys.len() - 1);
+
+        match *button {
+            EditorButton::CycleSwingType => {
+                editor.swing_type = swing_type.next();
+                editor.selected_key = 0;
+                editor.status.clear();
+            }
+            EditorButton::CycleEasing => curve.easing = curve.easing.next(),
+            EditorButton::ToggleConstantSpeed => curve.constant_speed = !curve.constant_speed,
+            EditorButton::ToggleOrientation => {
+                curve.orientation = match curve.orientation {
+                    BladeOrientation::RotationKeys => BladeOrientation::FollowPath { offset: 0.0 },
+                    BladeOrientation::FollowPath { .. } => BladeOrientation::RotationKeys,
+                };
+            }
+            EditorButton::TangentOffset(step) => {
+                if let BladeOrientation::FollowPath { offset } = &mut curve.orientation {
+                    *offset += step * KEY_ANGLE_STEP;
+                }
+            }
+            EditorButton::CycleKeyEasing => {
+                let key = &mut curve.rotation_keys[selected];
+                key.easing = key.easing.next();
+            }
+            EditorButton::SelectKey(step) => {
+                let count = curve.rotation_keys.len() as isize;
+                editor.selected_key = (selected as isize + step).rem_euclid(count) as usize;
+            }
+            EditorButton::KeyTime(step) => {
+                let key = &mut curve.rotation_keys[selected];
+                key.time = (key.time + step * KEY_TIME_STEP).clamp(0.0, 1.0);
+                let key = *key;
+                curve.sort_rotation_keys();
+                // Keep the same key selected after it moves past a neighbour
+                editor.selected_key = find_key(curve, key).unwrap_or(selected);
+            }
+            EditorButton::KeyAngle(step) => {
+                curve.rotation_keys[selected].angle += step * KEY_ANGLE_STEP;
+            }
+            EditorButton::AddKey => {
+                // Halfway to the next key, or to the end of the swing
+                let key = curve.rotation_keys[selected];
+                let next_time = curve.rotation_keys.get(selected + 1).map_or(1.0, |k| k.time);
+                let time = (key.time + next_time) / 2.0;
+                // Splitting a segment keeps its easing on both halves
+                let new_key = RotationKey {
+                    easing: key.easing,
+                    ..RotationKey::new(time, curve.rotation_at(time))
+                };
+                curve.rotation_keys.insert(selected + 1, new_key);
+                editor.selected_key = selected + 1;
+            }
+            EditorButton::RemoveKey => {
+                // A track needs a start and an end
+                if curve.rotation_keys.len() > 2 {
+                    curve.rotation_keys.remove(selected);
+                    editor.selected_key = selected.saturating_sub(1);
+                }
+            }
+            EditorButton::Save => {
+                let path = swing_type.asset_path();
+                editor.status = match curve.save(&path) {
+                    Ok(()) => {
+                        info!(target: "swing", "Saved swing curve to {}", path.display());
+                        format!("Saved to {}", path.display())
+                    }
+                    Err(error) => {
+                        warn!(target: "swing", "Failed to save swing curve: {}", error);
+                        format!("Save failed: {}", error)
+                    }
+                };
+            }
+            EditorButton::Revert => {
+                // Back to the saved asset, or the built-in curve if there is none
+                *curve = SwingCurves::load_or_default().get(swing_type).clone();
+                editor.selected_key = 0;
+                editor.status = "Reverted".to_string();
+            }
+        }
+    }
+}
+
+fn find_key(curve: &SwingCurve, key: RotationKey) -> Option<usize> {
+    curve
+        .rotation_keys
+        .iter()
+        .position(|k| k.time == key.time && k.angle == key.angle)
+}
+
+// Arrow keys step through the timeline; pressing on the timeline bar jumps to that point
+// and keeps following the cursor until the button is released
+fn scrub_timeline(
+    time: Res<Time<Real>>, // Virtual time is paused while editing
+    keys: Res<ButtonInput<KeyCode>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    bar_query: Query<(&Interaction, &ComputedNode, &GlobalTransform), With<TimelineBar>>,
+    mut preview: ResMut<SwingPreview>,
+) {
+    let mut scrub = 0.0;
+    if keys.pressed(KeyCode::ArrowLeft) {
+        scrub -= 1.0;
+    }
+    if keys.pressed(KeyCode::ArrowRight) {
+        scrub += 1.0;
+    }
+    if scrub != 0.0 {
+        preview.time = (preview.time + scrub * SCRUB_SPEED * time.delta_secs()).clamp(0.0, 1.0);
+    }
+
+    let Ok((interaction, node, transform)) = bar_query.single() else {
+        return;
+    };
+    if *interaction != Interaction::Pressed {
+        return;
+    }
+    let Some(cursor) = window_query.single().ok().and_then(|w| w.physical_cursor_position())
+    else {
+        return;
+    };
+    // UI nodes are positioned by their center, in physical pixels
+    let width = node.size().x;
+    let left = transform.translation().x - width / 2.0;
+    if width > 0.0 {
+        preview.time = ((cursor.x - left) / width).clamp(0.0, 1.0);
+    }
+}
+
+// The previewed sword: the first attached sword of the first node
+fn preview_sword<'a>(
+    swords: impl Iterator<Item = (Entity, &'a Sword, &'a ChildOf)>,
+) -> Option<(Entity, Entity)> {
+    swords
+        .min_by_key(|(entity, sword, _)| (sword.slot, *entity))
+        .map(|(entity, _, child_of)| (entity, child_of.parent()))
+}
+
+fn drag_control_points(
+    mouse: Res<ButtonInput<MouseButton>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+    ui_query: Query<&Interaction, Or<(With<EditorButton>, With<TimelineBar>)>>,
+    editor: Res<SwingEditor>,
+    mut preview: ResMut<SwingPreview>,
+    mut curves: ResMut<SwingCurves>,
+    sword_query: Query<(Entity, &Sword, &ChildOf, &SwingAnimation), Without<ThrownSword>>,
+    node_query: Query<&GlobalTransform, With<SwordNode>>,
+) {
+    if !mouse.pressed(MouseButton::Left) {
+        preview.dragging = None;
+        return;
+    }
+    let Some((sword_entity, node_entity)) =
+        preview_sword(sword_query.iter().map(|(e, s, c, _)| (e, s, c)))
+    else {
+        return;
+    };
+    let (Ok((_, _, _, swing)), Ok(node_transform)) =
+        (sword_query.get(sword_entity), node_query.get(node_entity))
+    else {
+        return;
+    };
+    let Some(cursor) = cursor_world_position(&window_query, &camera_query) else {
+        return;
+    };
+    // Cursor in the swing's space: relative to the windup pose under the node, and undoing
+    // the weapon's reach and the node's facing so the saved curve stays as authored
+    let cursor = node_transform.affine().inverse().transform_point3(cursor.extend(0.0));
+    let local = swing.node_to_curve(cursor.truncate());
+
+    if mouse.just_pressed(MouseButton::Left) {
+        // Presses on the panel or the timeline are handled by the UI
+        if ui_query.iter().any(|i| *i != Interaction::None) {
+            return;
+        }
+        // The first point is the windup pose itself and stays put
+        let curve = curves.get(editor.swing_type);
+        preview.dragging = (1..curve.path().points.len())
+            .map(|i| (i, curve.path().handle(i).distance(local)))
+            .filter(|(_, distance)| *distance * swing.arc_scale() <= GRAB_RADIUS)
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(i, _)| i);
+    }
+    // Only borrowed mutably when a point actually moves; marking the curves changed
+    // rebuilds the panel
+    if let Some(index) = preview.dragging {
+        let point = local.round();
+        if curves.get(editor.swing_type).path().handle(index) != point {
+            curves.get_mut(editor.swing_type).set_handle(index, point);
+        }
+    }
+}
+
+fn cursor_world_position(
+    window_query: &Query<&Window, With<PrimaryWindow>>,
+    camera_query: &Query<(&Camera, &GlobalTransform)>,
+) -> Option<Vec2> {
+    let cursor = window_query.single().ok()?.cursor_position()?;
+    let (camera, camera_transform) = camera_query.single().ok()?;
+    camera.viewport_to_world_2d(camera_transform, cursor).ok()
+}
+
+// Fractions of the timeline taken by startup, swing and recovery
+fn phase_fractions(tuning: &SwingTuning) -> [f32; 3] {
+    let durations = [tuning.startup, tuning.swing, tuning.recovery];
+    let total: f32 = durations.iter().sum();
+    if total <= 0.0 {
+        return [0.0, 1.0, 0.0];
+    }
+    durations.map(|d| d / total)
+}
+
+// Pose relative to the node at a point on the timeline; the same legs animate_sword_swing
+// plays, without charge
+fn pose_at(
+    time: f32,
+    tuning: &SwingTuning,
+    curve: &SwingCurve,
+    swing: &SwingAnimation,
+    sword: &Sword,
+) -> (Vec2, f32) {
+    let [startup, swing_phase, _] = phase_fractions(tuning);
+    let (rest_pos, rest_rotation) = sword.rest_pose();
+    let windup = swing.windup_pose();
+    let end = swing.swing_pose(curve, 1.0);
+
+    if time < startup {
+        let t = time / startup;
+        let rotation = lerp_angle(rest_rotation, windup.rotation, t);
+        (rest_pos.lerp(windup.position, t), rotation)
+    } else if time < startup + swing_phase {
+        let pose = swing.swing_pose(curve, (time - startup) / swing_phase);
+        (pose.position, pose.rotation)
+    } else {
+        let recovery = 1.0 - startup - swing_phase;
+        let t = if recovery > 0.0 {
+            ((time - startup - swing_phase) / recovery).min(1.0)
+        } else {
+            1.0
+        };
+        let rotation = lerp_angle(end.rotation, rest_rotation, t);
+        (end.position.lerp(rest_pos, t), rotation)
+    }
+}
+
+fn preview_swing_pose(
+    editor: Res<SwingEditor>,
+    preview: Res<SwingPreview>,
+    tuning: Res<SwingTuning>,
+    curves: Res<SwingCurves>,
+    mut sword_query: Query<
+        (Entity, &Sword, &ChildOf, &SwingAnimation, &mut Transform),
+        Without<ThrownSword>,
+    >,
+) {
+    let Some((sword_entity, _)) =
+        preview_sword(sword_query.iter().map(|(e, s, c, _, _)| (e, s, c)))
+    else {
+        return;
+    };
+    let Ok((_, sword, _, swing, mut transform)) = sword_query.get_mut(sword_entity) else {
+        return;
+    };
+    let curve = curves.get(editor.swing_type);
+    let (position, rotation) = pose_at(preview.time, &tuning, curve, swing, sword);
+    transform.translation = position.extend(transform.translation.z);
+    transform.rotation = Quat::from_rotation_z(rotation);
+}
+
+fn spawn_timeline(mut commands: Commands) {
+    let text_font = TextFont {
+        font_size: 12.0,
+        ..default()
+    };
+    let phase_colors = [
+        Color::srgb(0.45, 0.45, 0.45),
+        Color::srgb(0.8, 0.2, 0.2),
+        Color::srgb(0.45, 0.45, 0.45),
+    ];
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Percent(20.0),
+                right: Val::Percent(20.0),
+                bottom: Val::Px(30.0),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(4.0),
+                padding: UiRect::all(Val::Px(8.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+            Visibility::Hidden,
+            Timeline,
+        ))
+        .with_children(|timeline| {
+            timeline.spawn((Text::new(""), text_font.clone(), TimelineLabel));
+            timeline
+                .spawn((
+                    Node {
+                        width: Val::Percent(100.0),
+                        height: Val::Px(16.0),
+                        ..default()
+                    },
+                    Interaction::default(), // Pressed while scrubbing with the mouse
+                    TimelineBar,
+                ))
+                .with_children(|bar| {
+                    for (phase, color) in phase_colors.into_iter().enumerate() {
+                        bar.spawn((
+                            Node {
+                                height: Val::Percent(100.0),
+                                ..default()
+                            },
+                            BackgroundColor(color),
+                            TimelineSegment(phase),
+                        ));
+                    }
+                    bar.spawn((
+                        Node {
+                            position_type: PositionType::Absolute,
+                            width: Val::Px(2.0),
+                            height: Val::Percent(100.0),
+                            ..default()
+                        },
+                        BackgroundColor(Color::WHITE),
+                        TimelineMarker,
+                    ));
+                });
+        });
+}
+
+fn update_timeline(
+    editor: Res<SwingEditor>,
+    preview: Res<SwingPreview>,
+    tuning: Res<SwingTuning>,
+    mut segment_query: Query<(&mut Node, &TimelineSegment), Without<TimelineMarker>>,
+    mut marker_query: Query<&mut Node, With<TimelineMarker>>,
+    mut label_query: Query<&mut Text, With<TimelineLabel>>,
+) {
+    let fractions = phase_fractions(&tuning);
+    for (mut node, segment) in segment_query.iter_mut() {
+        node.width = Val::Percent(fractions[segment.0] * 100.0);
+    }
+    for mut node in marker_query.iter_mut() {
+        node.left = Val::Percent(preview.time * 100.0);
+    }
+
+    let phase = if preview.time < fractions[0] {
+        "Startup"
+    } else if preview.time < fractions[0] + fractions[1] {
+        "Swing"
+    } else {
+        "Recovery"
+    };
+    let total = tuning.startup + tuning.swing + tuning.recovery;
+    for mut text in label_query.iter_mut() {
+        text.0 = format!(
+            "{:?} swing - {} {:.3}s / {:.3}s (arrows or click to scrub, F4 to close)",
+            editor.swing_type,
+            phase,
+            preview.time * total,
+            total,
+        );
+    }
+}
+
+// The swing path with draggable control points, and the blade direction at each rotation key
+fn draw_editor_gizmos(
+    mut gizmos: Gizmos,
+    editor: Res<SwingEditor>,
+    preview: Res<SwingPreview>,
+    curves: Res<SwingCurves>,
+    sword_query: Query<(Entity, &Sword, &ChildOf, &SwingAnimation), Without<ThrownSword>>,
+    node_query: Query<&GlobalTransform, With<SwordNode>>,
+) {
+    let Some((sword_entity, node_entity)) =
+        preview_sword(sword_query.iter().map(|(e, s, c, _)| (e, s, c)))
+    else {
+        return;
+    };
+    let (Ok((_, _, _, swing)), Ok(node_transform)) =
+        (sword_query.get(sword_entity), node_query.get(node_entity))
+    else {
+        return;
+    };
+    let to_world = |local: Vec2| {
+        let point = swing.curve_to_node(local);
+        node_transform.transform_point(point.extend(0.0)).truncate()
+    };
+    let curve = curves.get(editor.swing_type);
+
+    gizmos.linestrip_2d(
+        (0..=PATH_SAMPLES).map(|i| to_world(curve.position_at(i as f32 / PATH_SAMPLES as f32))),
+        Color::srgb(1.0, 0.2, 0.2),
+    );
+
+    let path = curve.path();
+    let handles: Vec<Vec2> = (0..path.points.len()).map(|i| to_world(path.handle(i))).collect();
+    let cyan = Color::srgb(0.2, 0.8, 1.0);
+    if path.kind != SplineKind::CatmullRom {
+        gizmos.linestrip_2d(handles.iter().copied(), cyan.with_alpha(0.4));
+    }
+    for (index, point) in handles.into_iter().enumerate() {
+        let color = if preview.dragging == Some(index) {
+            Color::WHITE
+        } else {
+            cyan
+        };
+        if index == 0 {
+            gizmos.cross_2d(point, 8.0, cyan); // Windup pose, fixed
+        } else if path.is_anchor(index) {
+            gizmos.circle_2d(point, GRAB_RADIUS, color);
+        } else {
+            gizmos.rect_2d(point, Vec2::splat(GRAB_RADIUS * 1.4), color);
+        }
+    }
+
+    // Keys sit on the path at their (eased) progress; they only steer the blade when it isn't
+    // following the path
+    if curve.orientation != BladeOrientation::RotationKeys {
+        return;
+    }
+    for (index, key) in curve.rotation_keys.iter().enumerate() {
+        let origin = to_world(curve.position_at(key.time));
+        let rotation = (swing.start_rotation + key.angle) * swing.facing;
+        let direction = Vec2::from_angle(rotation + FRAC_PI_2);
+        let color = if index == editor.selected_key {
+            Color::srgb(1.0, 0.9, 0.2)
+        } else {
+            Color::srgb(0.9, 0.6, 0.2)
+        };
+        gizmos.line_2d(origin, origin + direction * 30.0, color);
+        gizmos.circle_2d(origin, 3.0, color);
+    }
+}
+
+// Rebuild the panel whenever the editor opens, closes or the curve it shows changes
+fn refresh_editor_panel(
+    mut commands: Commands,
+    editor: Res<SwingEditor>,
+    curves: Res<SwingCurves>,
+    panel_query: Query<Entity, With<EditorPanel>>,
+) {
+    if !editor.is_changed() && !curves.is_changed() {
+        return;
+    }
+    for panel in panel_query.iter() {
+        commands.entity(panel).despawn();
+    }
+    if editor.open {
+        spawn_editor_panel(&mut commands, &editor, curves.get(editor.swing_type));
+    }
+}
+
+fn spawn_editor_panel(commands: &mut Commands, editor: &SwingEditor, curve: &SwingCurve) {
+    let text_font = TextFont {
+        font_size: 12.0,
+        ..default()
+    };
+
+    // Bottom right, clear of the HUD, the bindings menu and the timeline
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                right: Val::Px(20.0),
+                bottom: Val::Px(100.0),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(4.0),
+                padding: UiRect::all(Val::Px(8.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.85)),
+            EditorPanel,
+        ))
+        .with_children(|panel| {
+            let title = format!("Swing editor: {:?}", editor.swing_type);
+            spawn_editor_row(panel, &text_font, title, |row| {
+                spawn_editor_button(row, "Next", EditorButton::CycleSwingType, &text_font);
+            });
+            panel.spawn((
+                Text::new("Drag the control points; the windup point stays fixed"),
+                text_font.clone(),
+            ));
+            panel.spawn((Text::new(format!("{:?} spline", curve.path().kind)), text_font.clone()));
+            for (index, point) in curve.path().points.iter().enumerate().skip(1) {
+                panel.spawn((
+                    Text::new(format!("P{}: ({:.0}, {:.0})", index, point.x, point.y)),
+                    text_font.clone(),
+                ));
+            }
+
+            spawn_editor_row(panel, &text_font, format!("Easing: {:?}", curve.easing), |row| {
+                spawn_editor_button(row, "Next", EditorButton::CycleEasing, &text_font);
+            });
+            let speed = if curve.constant_speed { "constant" } else { "parametric" };
+            spawn_editor_row(panel, &text_font, format!("Speed: {}", speed), |row| {
+                spawn_editor_button(row, "Toggle", EditorButton::ToggleConstantSpeed, &text_font);
+            });
+            let orientation = match curve.orientation {
+                BladeOrientation::RotationKeys => "Blade: rotation keys",
+                BladeOrientation::FollowPath { .. } => "Blade: follows path",
+            };
+            spawn_editor_row(panel, &text_font, orientation.to_string(), |row| {
+                spawn_editor_button(row, "Toggle", EditorButton::ToggleOrientation, &text_font);
+            });
+            if let BladeOrientation::FollowPath { offset } = curve.orientation {
+                let label = format!("Offset: {:.3} turns", offset / TAU);
+                spawn_editor_row(panel, &text_font, label, |row| {
+                    spawn_editor_button(row, "-", EditorButton::TangentOffset(-1.0), &text_font);
+                    spawn_editor_button(row, "+", EditorButton::TangentOffset(1.0), &text_font);
+                });
+            }
+
+            panel.spawn((Text::new("Rotation keys (progress: turns)"), text_font.clone()));
+            for (index, key) in curve.rotation_keys.iter().enumerate() {
+                let marker = if index == editor.selected_key { ">" } else { " " };
+                panel.spawn((
+                    Text::new(format!(
+                        "{} {:.2}: {:.3} ({:?})",
+                        marker,
+                        key.time,
+                        key.angle / TAU,
+                        key.easing
+                    )),
+                    text_font.clone(),
+                ));
+            }
+
+            spawn_editor_row(panel, &text_font, "Key".to_string(), |row| {
+                spawn_editor_button(row, "Prev", EditorButton::SelectKey(-1), &text_font);
+                spawn_editor_button(row, "Next", EditorButton::SelectKey(1), &text_font);
+                spawn_editor_button(row, "Add", EditorButton::AddKey, &text_font);
+                spawn_editor_button(row, "Remove", EditorButton::RemoveKey, &text_font);
+            });
+            spawn_editor_row(panel, &text_font, "Progress".to_string(), |row| {
+                spawn_editor_button(row, "-", EditorButton::KeyTime(-1.0), &text_font);
+                spawn_editor_button(row, "+", EditorButton::KeyTime(1.0), &text_font);
+            });
+            spawn_editor_row(panel, &text_font, "Angle".to_string(), |row| {
+                spawn_editor_button(row, "-", EditorButton::KeyAngle(-1.0), &text_font);
+                spawn_editor_button(row, "+", EditorButton::KeyAngle(1.0), &text_font);
+            });
+            spawn_editor_row(panel, &text_font, "Easing to next".to_string(), |row| {
+                spawn_editor_button(row, "Next", EditorButton::CycleKeyEasing, &text_font);
+            });
+
+            panel
+                .spawn(Node {
+                    column_gap: Val::Px(6.0),
+                    ..default()
+                })
+                .with_children(|row| {
+                    spawn_editor_button(row, "Save", EditorButton::Save, &text_font);
+                    spawn_editor_button(row, "Revert", EditorButton::Revert, &text_font);
+                });
+            if !editor.status.is_empty() {
+                panel.spawn((Text::new(editor.status.clone()), text_font.clone()));
+            }
+        });
+}
+
+fn spawn_editor_row(
+    parent: &mut ChildSpawnerCommands,
+    text_font: &TextFont,
+    label: String,
+    buttons: impl FnOnce(&mut ChildSpawnerCommands),
+) {
+    parent
+        .spawn(Node {
+            column_gap: Val::Px(6.0),
+            align_items: AlignItems::Center,
+            ..default()
+        })
+        .with_children(|row| {
+            row.spawn((
+                Text::new(label),
+                text_font.clone(),
+                Node {
+                    width: Val::Px(120.0),
+                    ..default()
+                },
+            ));
+            buttons(row);
+        });
+}
+
+fn spawn_editor_button(
+    parent: &mut ChildSpawnerCommands,
+    label: &str,
+    button: EditorButton,
+    text_font: &TextFont,
+) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                padding: UiRect::axes(Val::Px(6.0), Val::Px(1.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.25, 0.25, 0.3)),
+            button,
+        ))
+        .with_child((Text::new(label), text_font.clone()));
+}
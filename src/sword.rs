@@ -1,44 +1,83 @@
+use crate::gamepad::GamepadState;
+use crate::parry::SwordStance;
+use crate::player::PlayerMovement;
 use crate::swing_animation::SwingAnimation;
+use crate::swing_animation::SwingMoment;
 use crate::swing_animation::SwingType;
 use crate::swing_animation::SwingPhase;
+use crate::swing_animation::SwingPose;
+use crate::swing_animation::SwingTimeScale;
+use crate::swing_editor::swing_editor_closed;
+use crate::sword_collider::HitRegistry;
+use crate::sword_formation::SwordFormation;
+use crate::weapon::{Weapon, WeaponKind};
 use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
 use std::f32::consts::PI;
 
+// Windup pose of a lone sword; in a formation each slot winds up offset from it
+pub const WINDUP_POS: Vec2 = Vec2::new(-20.0, 60.0); // Back and up
+pub const WINDUP_ROTATION: f32 = -PI * 0.2; // Rotated back (~-36 degrees)
+
 #[derive(Component)]
 pub struct Sword {
-    pub offset: Vec2,
+    pub offset: Vec2,       // Rest position relative to the node
+    pub rest_rotation: f32, // Rest rotation relative to the node
+    pub slot: usize,        // Position of this sword in its node's formation
 }
 
 impl Default for Sword {
     fn default() -> Self {
         Self {
             offset: Vec2::new(20.0, -10.0), // Default offset from node position
+            rest_rotation: -PI * 0.1,
+            slot: 0,
         }
     }
 }
 
+impl Sword {
+    // The pose the sword idles at and returns to after a swing
+    pub fn rest_pose(&self) -> (Vec2, f32) {
+        (self.offset, self.rest_rotation)
+    }
+}
+
 // New component to mark the intermediate node
 #[derive(Component)]
 pub struct SwordNode {
     pub locked_position: Option<Vec2>, // Store locked position during swing
+    pub facing: f32,                   // 1.0 = swords rest right of the node, -1.0 = left
 }
 
 impl Default for SwordNode {
     fn default() -> Self {
         Self {
             locked_position: None,
+            facing: 1.0,
         }
     }
 }
 
+// Shared sprite handles so extra swords can be summoned at runtime
+#[derive(Resource)]
+pub struct SwordAssets {
+    pub texture: Handle<Image>,
+    pub layout: Handle<TextureAtlasLayout>,
+}
+
 pub struct SwordPlugin;
 
 impl Plugin for SwordPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(Startup, spawn_sword_with_node).add_systems(
             Update,
-            (update_node_position, check_swing_status).chain(),
+            (
+                // The editor holds the node still so its control points can be dragged
+                update_node_position.run_if(swing_editor_closed),
+                check_swing_status,
+            )
+                .chain(),
         );
     }
 }
@@ -52,6 +91,10 @@ fn spawn_sword_with_node(
     let texture = asset_server.load("sword.png");
     let layout = TextureAtlasLayout::from_grid(UVec2::splat(64), 1, 1, None, None);
     let texture_atlas_layout = texture_atlas_layouts.add(layout);
+    let sword_assets = SwordAssets {
+        texture,
+        layout: texture_atlas_layout,
+    };
 
     // Spawn the intermediate node (invisible parent)
     let node_entity = commands
@@ -59,116 +102,149 @@ fn spawn_sword_with_node(
             Transform::from_xyz(0.0, 0.0, 0.0),
             GlobalTransform::default(),
             SwordNode::default(),
+            SwordFormation::default(),
         ))
         .id();
 
-    // Spawn the sword as a child of the node
+    spawn_sword(&mut commands, &sword_assets, node_entity, 0, WeaponKind::default());
+    commands.insert_resource(sword_assets);
+}
+
+// Spawn a sword as a child of the node, occupying the given formation slot. The weapon's
+// sprite, collider and stats are applied by equip_weapons once it exists.
+pub fn spawn_sword(
+    commands: &mut Commands,
+    sword_assets: &SwordAssets,
+    node_entity: Entity,
+    slot: usize,
+    weapon: WeaponKind,
+) -> Entity {
+    let sword = Sword {
+        slot,
+        ..default()
+    };
+    let (rest_pos, rest_rotation) = sword.rest_pose();
     let sword_entity = commands
         .spawn((
             Sprite {
-                image: texture,
+                image: sword_assets.texture.clone(),
                 texture_atlas: Some(TextureAtlas {
-                    layout: texture_atlas_layout,
+                    layout: sword_assets.layout.clone(),
                     index: 0,
                 }),
                 ..default()
             },
-            Transform::from_xyz(0.0, 0.0, 1.0), // Initial position relative to node
+            Transform::from_xyz(rest_pos.x, rest_pos.y, 1.0)
+                .with_rotation(Quat::from_rotation_z(rest_rotation)), // Start at rest pose
         SwingAnimation {
-            timer: Timer::from_seconds(0.8, TimerMode::Once), // Total animation time (legacy)
             
             // Phase 1: Startup - Move to attack position
             startup_timer: Timer::from_seconds(0.1, TimerMode::Once), // 200ms to reach attack position
-            start_pos: Vec2::new(-20.0, 60.0), // Back and up for windup
-            start_rotation: -PI * 0.2, // Rotated back (~-54 degrees)
+            start_pos: WINDUP_POS, // Set per formation slot as each swing starts
+            start_rotation: WINDUP_ROTATION,
+            facing: 1.0,
             
             // Phase 2: Main swing
             swing_timer: Timer::from_seconds(0.4, TimerMode::Once), // 400ms for main swing
             
             // Phase 3: Recovery - Move to rest position  
             end_timer: Timer::from_seconds(0.2, TimerMode::Once), // 200ms to reach rest position
+            // Delay before startup, used to stagger swords swinging in sequence
+            delay_timer: Timer::from_seconds(0.0, TimerMode::Once),
             // State tracking
             is_swinging: false,
             swing_type: SwingType::Vertical,
             current_phase: SwingPhase::Startup, // Will be set properly in start_swing()
-            swing_end_pos: Vec2::ZERO,
-            swing_end_rotation: 0.0,
+            phase_from: SwingPose::default(),
+            blend: None,
+            pending_transition: None,
+            notified_until: SwingMoment::START,
+            interrupted: false,
+            pending_finish: false,
+            is_charging: false,
+            charge: 0.0,
+            full_charge_time: 0.8,
+            time_scale: SwingTimeScale::default(),
+            reach: 1.0,
         },
-            Sword::default(),
+            sword,
+            HitRegistry::default(),
+            SwordStance::default(),
+            Weapon::new(weapon),
         ))
         .id();
 
     // Make sword a child of the node
     commands.entity(node_entity).add_child(sword_entity);
+    sword_entity
 }
 
-// Check swing status and manage position locking
 // Check swing status and manage position locking
 fn check_swing_status(
-    mut node_query: Query<(&mut SwordNode, &Children, &Transform)>,
+    mut node_query: Query<(Entity, &mut SwordNode, &Children, &Transform)>,
     swing_query: Query<&SwingAnimation>,
-    window_query: Query<&mut Window, With<PrimaryWindow>>,
-    camera_query: Query<(&Camera, &GlobalTransform)>,
 ) {
-    // Collect positions that need to be set to avoid borrowing issues
-    
-    for (mut sword_node, children, node_transform) in node_query.iter_mut() {
+    for (node, mut sword_node, children, node_transform) in node_query.iter_mut() {
         // Find the sword child and check its swing status
         let mut is_currently_swinging = false;
         let was_swinging = sword_node.locked_position.is_some();
         
+        // The node stays locked while any of its swords is still swinging
         for child in children.iter() {
-            if let Ok(swing_animation) = swing_query.get(child) {
-                is_currently_swinging = swing_animation.is_swinging;
+            if swing_query.get(child).is_ok_and(|swing_animation| swing_animation.is_swinging) {
+                is_currently_swinging = true;
                 break;
             }
         }
 
-        // If starting to swing and not already locked, lock the current mouse position
+        // If starting to swing and not already locked, lock where the node is aimed right now
+        // (the cursor, or the right-stick position when playing on a gamepad)
         if is_currently_swinging && sword_node.locked_position.is_none() {
-            if let (Ok(window), Ok((camera, camera_transform))) = 
-                (window_query.single(), camera_query.single()) {
-                if let Some(cursor_pos) = window.cursor_position() {
-                    if let Ok(world_pos) = camera.viewport_to_world_2d(camera_transform, cursor_pos) {
-                        sword_node.locked_position = Some(world_pos);
-                    }
-                }
-            }
+            let position = node_transform.translation.truncate();
+            sword_node.locked_position = Some(position);
+            debug!(target: "sword", ?node, ?position, "node locked");
         }
         
-        // If swing just finished, calculate and set the offset
+        // If swing just finished, release the lock so the node follows the cursor again.
+        // The rest offsets are re-evaluated by arrange_formation on the next pass.
         if was_swinging && !is_currently_swinging && sword_node.locked_position.is_some() {
-            // Store the position to set later
-            let node_pos = Vec2::new(node_transform.translation.x, node_transform.translation.y);
-            println!("Swing finished - Node position: {:?}", node_pos);
-            
-            // Clear the lock
             sword_node.locked_position = None;
+            debug!(target: "sword", ?node, "node unlocked");
         }
     }
-    
 }
 
-// Update the node position to follow the mouse (only when not locked)
+// Update the node position to follow the mouse or right stick (only when not locked)
 fn update_node_position(
     mut node_query: Query<(&mut Transform, &mut SwordNode), Without<Sword>>,
     window_query: Query<&Window, With<PrimaryWindow>>,
     camera_query: Query<(&Camera, &GlobalTransform)>,
+    player_query: Query<&Transform, (With<PlayerMovement>, Without<SwordNode>, Without<Sword>)>,
+    gamepad: Res<GamepadState>,
 ) {
-    let Ok(window) = window_query.single() else {
-        return;
-    };
-    let Some(cursor_pos) = window.cursor_position() else {
-        return;
-    };
-    let Ok((camera, camera_transform)) = camera_query.single() else {
-        return;
-    };
-    let Ok(world_pos) = camera.viewport_to_world_2d(camera_transform, cursor_pos) else {
-        return;
+    let aim_pos = if gamepad.aiming_with_stick {
+        // Right stick places the node around the player
+        let Ok(player_transform) = player_query.single() else {
+            return;
+        };
+        player_transform.translation.truncate() + gamepad.aim_offset
+    } else {
+        let Ok(window) = window_query.single() else {
+            return;
+        };
+        let Some(cursor_pos) = window.cursor_position() else {
+            return;
+        };
+        let Ok((camera, camera_transform)) = camera_query.single() else {
+            return;
+        };
+        let Ok(world_pos) = camera.viewport_to_world_2d(camera_transform, cursor_pos) else {
+            return;
+        };
+        world_pos
     };
 
-    for (mut transform, mut sword_node) in node_query.iter_mut() {
+    for (mut transform, sword_node) in node_query.iter_mut() {
         // If we have a locked position, use that instead of mouse position
         if let Some(locked_pos) = sword_node.locked_position {
             let target_pos = locked_pos;
@@ -176,10 +252,10 @@ fn update_node_position(
             transform.translation.y = target_pos.y;
             
         } else {
-            // Normal mouse following when not locked, but apply the offset
-            let target_pos = world_pos;
+            // Normal following when not locked
+            let target_pos = aim_pos;
             transform.translation.x = target_pos.x;
             transform.translation.y = target_pos.y;
         }
     }
-}
\ No newline at end of file
+}
@@ -1,66 +1,166 @@
+use crate::damage::{DamageEvent, Resistances};
 use crate::enemy::Enemy;
+use crate::parry::SwordStance;
+use crate::swing_animation::{SwingAnimation, SwingCurves};
 use crate::sword::Sword;
+use crate::sword_throw::{ThrowState, ThrownSword};
+use crate::weapon::{BladeContact, Weapon};
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
+use std::collections::HashSet;
 
 pub struct SwordColliderPlugin;
 
 impl Plugin for SwordColliderPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(RapierPhysicsPlugin::<NoUserData>::pixels_per_meter(100.0))
-            .add_plugins(RapierDebugRenderPlugin {
-                enabled: true,
-                ..default()
-            })
-            .add_systems(PostStartup, setup_physics)
-            .add_systems(Update, handle_collisions);
+            // Runs every frame so summoned swords pick up their colliders too
+            .add_systems(Update, (setup_physics, handle_blade_hits));
     }
 }
 
-fn setup_physics(mut commands: Commands, sword_query: Query<(Entity, &Sword), Without<RigidBody>>) {
-    for (entity, sword) in sword_query.iter() {
+// Enemies a sword has already damaged during the current swing
+#[derive(Component, Default)]
+pub struct HitRegistry {
+    hits: HashSet<Entity>,
+}
+
+impl HitRegistry {
+    pub fn clear(&mut self) {
+        self.hits.clear();
+    }
+
+    // Returns false if the target was already hit since the last clear
+    pub fn register(&mut self, target: Entity) -> bool {
+        self.hits.insert(target)
+    }
+}
+
+pub fn setup_physics(
+    mut commands: Commands,
+    sword_query: Query<Entity, (With<Sword>, Without<RigidBody>)>,
+) {
+    for entity in sword_query.iter() {
         commands
             .entity(entity)
             .insert(RigidBody::KinematicPositionBased)
-            .insert(Collider::cuboid(10., 30.))
+            .insert(Collider::cuboid(10., 30.)) // Placeholder until the weapon is equipped
             .insert(Sensor) // Optional: makes it a sensor (no collision response, just detection)
+            // Kinematic-vs-fixed contacts are off by default; thrown swords need them to hit walls
+            .insert(ActiveCollisionTypes::default() | ActiveCollisionTypes::KINEMATIC_STATIC)
             .insert(ActiveEvents::COLLISION_EVENTS); // Enable collision events for this entity
     }
 }
 
-fn handle_collisions(
-    mut collision_events: EventReader<CollisionEvent>,
-    mut enemy_query: Query<&mut Enemy>,
-    sword_query: Query<&Sword>,
-    mut commands: Commands,
+// Blades cut whatever they overlap while their swing's hit window is open; thrown swords cut
+// while they are flying out or back, not while embedded. Overlaps are polled rather than
+// taken from collision events, so an enemy the blade already touched during the windup is
+// still cut when the window opens.
+fn handle_blade_hits(
+    rapier_context: ReadRapierContext,
+    curves: Res<SwingCurves>,
+    enemy_query: Query<(&Enemy, Option<&Resistances>, &GlobalTransform)>,
+    mut sword_query: Query<
+        (
+            Entity,
+            &mut HitRegistry,
+            &SwingAnimation,
+            &SwordStance,
+            &Weapon,
+            &Transform,
+            &GlobalTransform,
+            Option<(&ThrownSword, &Velocity)>,
+        ),
+        With<Sword>,
+    >,
+    mut damage_events: EventWriter<DamageEvent>,
 ) {
-    for collision_event in collision_events.read() {
-        match collision_event {
-            CollisionEvent::Started(entity1, entity2, _) => {
-                println!("Collision started between {:?} and {:?}", entity1, entity2);
-                let (sword_entity, enemy_entity) =
-                    if sword_query.contains(*entity1) && enemy_query.contains(*entity2) {
-                        (*entity1, *entity2)
-                    } else if sword_query.contains(*entity2) && enemy_query.contains(*entity1) {
-                        (*entity2, *entity1)
-                    } else {
-                        continue; // Not a sword-enemy collision
-                    };
+    let Ok(rapier_context) = rapier_context.single() else {
+        return;
+    };
+    for (sword_entity, mut hits, swing, stance, weapon, local, sword_transform, thrown) in
+        sword_query.iter_mut()
+    {
+        let curve = curves.get(swing.swing_type);
+        let cutting = match thrown {
+            Some((thrown, _)) => thrown.state != ThrowState::Embedded,
+            None => swing.in_hit_window(curve),
+        };
+        if !cutting {
+            continue;
+        }
+        // A guarding blade deflects instead of cutting
+        if stance.is_parrying() {
+            continue;
+        }
+
+        for (entity1, entity2, intersecting) in rapier_context.intersection_pairs_with(sword_entity)
+        {
+            let enemy_entity = if entity1 == sword_entity { entity2 } else { entity1 };
+            if !intersecting {
+                continue;
+            }
+            let Ok((enemy, resistances, enemy_transform)) = enemy_query.get(enemy_entity) else {
+                continue; // Not an enemy
+            };
+            // Already dead, despawned by update_enemies
+            if enemy.health <= 0.0 {
+                continue;
+            }
+            // Each sword damages an enemy at most once per swing
+            if !hits.register(enemy_entity) {
+                continue;
+            }
+            trace!(target: "collision", sword = ?sword_entity, enemy = ?enemy_entity, "blade hit");
 
-                if let Ok(mut enemy) = enemy_query.get_mut(enemy_entity) {
-                    enemy.health -= 50.0;
-                    println!("Enemy {:?} health: {}", enemy_entity, enemy.health);
+            // Sensors don't report contact points, so call the hit on the point of the
+            // blade closest to the enemy's centre
+            let sword_pos = sword_transform.translation().truncate();
+            let enemy_pos = enemy_transform.translation().truncate();
+            let axis = sword_transform.up().truncate().normalize_or_zero(); // Towards the tip
+            let half_length = weapon.blade_half_length.max(f32::EPSILON);
+            let distance = (enemy_pos - sword_pos).dot(axis).clamp(-half_length, half_length);
+            let position = sword_pos + axis * distance;
 
-                    if enemy.health <= 0.0 {
-                        commands.entity(enemy_entity).despawn();
-                        println!(
-                            "Enemy {:?} destroyed by sword {:?}!",
-                            enemy_entity, sword_entity
-                        );
-                    }
+            // A thrown sword flies as a whole; a held one moves as its swing makes it. The
+            // swing curve is in node space, so turn it by the node's rotation.
+            let blade_velocity = match thrown {
+                Some((_, velocity)) => velocity.linvel,
+                None => {
+                    let node_rotation = sword_transform.rotation() * local.rotation.inverse();
+                    let velocity = swing.blade_velocity(curve, distance);
+                    (node_rotation * velocity.extend(0.0)).truncate()
                 }
-            }
-            CollisionEvent::Stopped(entity1, entity2, _) => {}
+            };
+            let contact = BladeContact {
+                along: (distance + half_length) / (2.0 * half_length),
+                speed: blade_velocity.length(),
+            };
+            let crit_roll = rand::random::<f32>();
+            // Charged swings hit harder
+            let hit = weapon.hit(enemy, resistances, contact, swing.damage_multiplier(), crit_roll);
+            debug!(
+                target: "collision",
+                along = contact.along,
+                speed = contact.speed,
+                crit = hit.crit,
+                sweet_spot = hit.sweet_spot,
+                "blade contact"
+            );
+            damage_events.write(DamageEvent {
+                target: enemy_entity,
+                source: Some(sword_entity),
+                amount: hit.amount,
+                damage_type: weapon.damage_type,
+                crit: hit.crit,
+                sweet_spot: hit.sweet_spot,
+                position,
+                // The way the blade was moving, or straight through if it had stopped
+                direction: blade_velocity
+                    .try_normalize()
+                    .unwrap_or((enemy_pos - position).normalize_or_zero()),
+                indirect: false,
+            });
         }
     }
 }
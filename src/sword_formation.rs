@@ -0,0 +1,240 @@
+use crate::input_map::{Action, ActionState};
+use crate::player::PlayerMovement;
+use crate::swing_animation::SwingAnimation;
+use crate::sword::{spawn_sword, Sword, SwordAssets, SwordNode, WINDUP_POS, WINDUP_ROTATION};
+use crate::sword_throw::ThrownSword;
+use crate::weapon::Weapon;
+use bevy::prelude::*;
+use std::f32::consts::{PI, TAU};
+
+pub struct SwordFormationPlugin;
+
+impl Plugin for SwordFormationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<SummonSwordEvent>().add_systems(
+            Update,
+            (handle_formation_input, summon_swords, arrange_formation).chain(),
+        );
+    }
+}
+
+// How the swords attached to a node are laid out around it
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum FormationKind {
+    Fan,  // Spread like a hand of cards around the default rest pose
+    Ring, // Evenly spaced around the node, blades pointing outward
+    Line, // Stacked vertically beside the node
+}
+
+// Whether a swing command starts every sword at once or one after another
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum SwingMode {
+    Sequence,
+    Unison,
+}
+
+#[derive(Component, Clone)]
+pub struct SwordFormation {
+    pub kind: FormationKind,
+    pub swing_mode: SwingMode,
+    pub fan_spread: f32,     // Angle between neighbouring blades in a fan (radians)
+    pub ring_radius: f32,    // Distance of each blade from the node in a ring
+    pub line_spacing: f32,   // Vertical gap between blades in a line
+    pub sequence_delay: f32, // Seconds between consecutive swords in Sequence mode
+}
+
+impl Default for SwordFormation {
+    fn default() -> Self {
+        Self {
+            kind: FormationKind::Fan,
+            swing_mode: SwingMode::Sequence,
+            fan_spread: PI * 0.15,
+            ring_radius: 40.0,
+            line_spacing: 24.0,
+            sequence_delay: 0.08,
+        }
+    }
+}
+
+impl SwordFormation {
+    // Rest pose of the given slot for a node facing right; mirrored by the caller
+    pub fn slot_pose(&self, slot: usize, count: usize) -> (Vec2, f32) {
+        let anchor = Sword::default();
+        let count = count.max(1);
+        // Slot index centered around zero, so a lone sword sits on the anchor pose
+        let centered = slot as f32 - (count - 1) as f32 / 2.0;
+
+        match self.kind {
+            FormationKind::Fan => {
+                let angle = centered * self.fan_spread;
+                (
+                    Vec2::from_angle(angle).rotate(anchor.offset),
+                    anchor.rest_rotation + angle,
+                )
+            }
+            FormationKind::Ring => {
+                let angle = slot as f32 / count as f32 * TAU;
+                // The sprite points up, so rotate it a quarter turn back to face outward
+                (Vec2::from_angle(angle) * self.ring_radius, angle - PI * 0.5)
+            }
+            FormationKind::Line => (
+                anchor.offset + Vec2::new(0.0, -centered * self.line_spacing),
+                anchor.rest_rotation,
+            ),
+        }
+    }
+
+    // Windup pose of the given slot: the lone sword's windup, moved and turned as far as the
+    // slot's rest pose is from the anchor, so the blades don't all cut along one path. For a
+    // node facing right, like slot_pose; the swing mirrors it along with its path.
+    pub fn windup_pose(&self, slot: usize, count: usize) -> (Vec2, f32) {
+        let anchor = Sword::default();
+        let (offset, rotation) = self.slot_pose(slot, count);
+        (
+            WINDUP_POS + offset - anchor.offset,
+            WINDUP_ROTATION + rotation - anchor.rest_rotation,
+        )
+    }
+
+    pub fn next_kind(&mut self) {
+        self.kind = match self.kind {
+            FormationKind::Fan => FormationKind::Ring,
+            FormationKind::Ring => FormationKind::Line,
+            FormationKind::Line => FormationKind::Fan,
+        };
+    }
+
+    pub fn toggle_swing_mode(&mut self) {
+        self.swing_mode = match self.swing_mode {
+            SwingMode::Sequence => SwingMode::Unison,
+            SwingMode::Unison => SwingMode::Sequence,
+        };
+    }
+
+    // Seconds the sword at `order` (0 = first to go) waits before its startup phase
+    pub fn swing_delay(&self, order: usize) -> f32 {
+        match self.swing_mode {
+            SwingMode::Sequence => order as f32 * self.sequence_delay,
+            SwingMode::Unison => 0.0,
+        }
+    }
+}
+
+// Swords thrown from the node and not caught yet keep their slot in its formation
+pub fn thrown_count(node: Entity, thrown_query: &Query<&ThrownSword>) -> usize {
+    thrown_query.iter().filter(|thrown| thrown.node == node).count()
+}
+
+// Request an extra sword on the given node, e.g. from an upgrade pickup
+#[derive(Event)]
+pub struct SummonSwordEvent {
+    pub node: Entity,
+}
+
+fn handle_formation_input(
+    actions: Res<ActionState>,
+    mut node_query: Query<(Entity, &mut SwordFormation), With<SwordNode>>,
+    mut summon_events: EventWriter<SummonSwordEvent>,
+) {
+    for (node, mut formation) in node_query.iter_mut() {
+        if actions.just_pressed(Action::SummonSword) {
+            summon_events.write(SummonSwordEvent { node });
+        }
+        if actions.just_pressed(Action::CycleFormation) {
+            formation.next_kind();
+        }
+        if actions.just_pressed(Action::ToggleSwingMode) {
+            formation.toggle_swing_mode();
+        }
+    }
+}
+
+fn summon_swords(
+    mut commands: Commands,
+    mut summon_events: EventReader<SummonSwordEvent>,
+    sword_assets: Option<Res<SwordAssets>>,
+    node_query: Query<Option<&Children>, With<SwordNode>>,
+    sword_query: Query<(&Sword, Option<&Weapon>)>,
+    thrown_query: Query<(&ThrownSword, &Sword)>,
+) {
+    let Some(sword_assets) = sword_assets else {
+        return;
+    };
+
+    // Several summons can target the same node in one frame, so track the slots handed out
+    let mut extra_slots: Vec<(Entity, usize)> = Vec::new();
+
+    for event in summon_events.read() {
+        let Ok(children) = node_query.get(event.node) else {
+            continue;
+        };
+        // Attached swords, thrown ones that will be caught again and earlier summons
+        let attached = children.into_iter().flat_map(|children| children.iter());
+        let taken: Vec<usize> = attached
+            .filter_map(|c| sword_query.get(c).ok().map(|(sword, _)| sword.slot))
+            .chain(
+                thrown_query
+                    .iter()
+                    .filter(|(thrown, _)| thrown.node == event.node)
+                    .map(|(_, sword)| sword.slot),
+            )
+            .chain(extra_slots.iter().filter(|(node, _)| *node == event.node).map(|(_, s)| *s))
+            .collect();
+        let slot = (0..).find(|slot| !taken.contains(slot)).unwrap_or_default();
+        // Summoned swords carry the same weapon as the rest of the node
+        let weapon = children
+            .and_then(|children| {
+                children.iter().find_map(|c| sword_query.get(c).ok().and_then(|(_, w)| w))
+            })
+            .map(|weapon| weapon.kind)
+            .unwrap_or_default();
+
+        spawn_sword(&mut commands, &sword_assets, event.node, slot, weapon);
+        extra_slots.push((event.node, slot));
+    }
+}
+
+// Keep each sword's rest pose on its formation slot, mirrored to the side facing away
+// from the player so the blades idle on the outside when the cursor crosses the player
+fn arrange_formation(
+    mut node_query: Query<(
+        Entity,
+        &Transform,
+        &Children,
+        &mut SwordNode,
+        Option<&SwordFormation>,
+    )>,
+    player_query: Query<&Transform, (With<PlayerMovement>, Without<SwordNode>)>,
+    mut sword_query: Query<(&mut Sword, &SwingAnimation)>,
+    thrown_query: Query<&ThrownSword>,
+) {
+    let player_pos = player_query.single().ok().map(|t| t.translation.truncate());
+    let default_formation = SwordFormation::default();
+
+    for (node, node_transform, children, mut sword_node, formation) in node_query.iter_mut() {
+        let formation = formation.unwrap_or(&default_formation);
+
+        // Only re-evaluate facing while the node follows the cursor
+        if let Some(player_pos) = player_pos {
+            let dx = node_transform.translation.x - player_pos.x;
+            if dx != 0.0 && sword_node.locked_position.is_none() {
+                sword_node.facing = dx.signum();
+            }
+        }
+        let facing = sword_node.facing;
+
+        let attached = children.iter().filter(|c| sword_query.contains(*c)).count();
+        let count = attached + thrown_count(node, &thrown_query);
+        for child in children.iter() {
+            if let Ok((mut sword, swing)) = sword_query.get_mut(child) {
+                // Never change the rest pose mid-swing, recovery is already heading to it
+                if swing.is_swinging {
+                    continue;
+                }
+                let (offset, rotation) = formation.slot_pose(sword.slot, count);
+                sword.offset = Vec2::new(offset.x * facing, offset.y);
+                sword.rest_rotation = rotation * facing;
+            }
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use crate::swing_animation::{animate_sword_swing, SwingAnimation, SwingPhase};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+pub struct SwordSpritePlugin;
+
+impl Plugin for SwordSpritePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, animate_sword_frames.after(animate_sword_swing));
+    }
+}
+
+// Atlas frames played in order. With an fps the sequence loops on its own clock; without
+// one it is stretched over its swing phase, so it follows the swing's timing exactly
+// (attack speed, charge and hitstop included).
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct FrameSequence {
+    pub frames: Vec<usize>, // Atlas indices
+    #[serde(default)]
+    pub fps: f32,
+}
+
+impl FrameSequence {
+    pub fn looping(frames: &[usize], fps: f32) -> Self {
+        Self {
+            frames: frames.to_vec(),
+            fps,
+        }
+    }
+
+    pub fn timeline(frames: &[usize]) -> Self {
+        Self::looping(frames, 0.0)
+    }
+
+    // Atlas index `progress` through the phase, or `elapsed` seconds into the loop
+    pub fn frame(&self, progress: f32, elapsed: f32) -> Option<usize> {
+        let count = self.frames.len();
+        if count == 0 {
+            return None;
+        }
+        let index = if self.fps > 0.0 {
+            (elapsed * self.fps) as usize % count
+        } else {
+            ((progress * count as f32) as usize).min(count - 1)
+        };
+        Some(self.frames[index])
+    }
+}
+
+// Frame sequences of a weapon's sprite sheet; an empty sequence leaves the frame as it is
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SwordAnimations {
+    pub idle: FrameSequence,     // At rest and while waiting for a turn in the sequence
+    pub startup: FrameSequence,  // The windup; its last frame is held while charging
+    pub swing: FrameSequence,    // The cut
+    pub recovery: FrameSequence, // Back to rest
+}
+
+impl SwordAnimations {
+    pub fn sequence(&self, phase: Option<SwingPhase>) -> &FrameSequence {
+        match phase {
+            None => &self.idle,
+            Some(SwingPhase::Startup) => &self.startup,
+            Some(SwingPhase::Swing) => &self.swing,
+            Some(SwingPhase::Recovery) => &self.recovery,
+        }
+    }
+}
+
+// Plays the equipped weapon's animations on the sword's sprite
+#[derive(Component)]
+pub struct SwordFrames {
+    pub animations: SwordAnimations,
+    pub frame_count: usize,    // Cells in the atlas; indices past it are ignored
+    phase: Option<SwingPhase>, // None while idle
+    elapsed: f32,              // Time in the current phase, for looping sequences
+}
+
+impl SwordFrames {
+    pub fn new(animations: SwordAnimations, frame_count: usize) -> Self {
+        Self {
+            animations,
+            frame_count,
+            phase: None,
+            elapsed: 0.0,
+        }
+    }
+}
+
+fn animate_sword_frames(
+    time: Res<Time>,
+    mut sword_query: Query<(&SwingAnimation, &mut SwordFrames, &mut Sprite)>,
+) {
+    for (swing, mut frames, mut sprite) in sword_query.iter_mut() {
+        let phase = (swing.is_swinging && swing.delay_timer.finished())
+            .then_some(swing.current_phase);
+        if phase != frames.phase {
+            frames.phase = phase;
+            frames.elapsed = 0.0;
+        }
+        // Loops freeze with the swing during hitstop
+        frames.elapsed += time.delta_secs() * swing.time_scale.rate();
+
+        let progress = if phase.is_some() {
+            swing.phase_progress()
+        } else {
+            0.0
+        };
+        let Some(index) = frames.animations.sequence(phase).frame(progress, frames.elapsed) else {
+            continue;
+        };
+        if index >= frames.frame_count {
+            continue;
+        }
+        let Some(atlas) = &mut sprite.texture_atlas else {
+            continue;
+        };
+        if atlas.index != index {
+            atlas.index = index;
+        }
+    }
+}
@@ -0,0 +1,198 @@
+use crate::arena::Wall;
+use crate::audio::SoundEvent;
+use crate::input_map::{Action, ActionState};
+use crate::player::PlayerMovement;
+use crate::swing_animation::SwingAnimation;
+use crate::sword::{Sword, SwordNode};
+use crate::sword_collider::HitRegistry;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+const THROW_SPEED: f32 = 900.0; // px/s on the way out
+const THROW_SPIN: f32 = 20.0; // rad/s while airborne
+const THROW_MAX_RANGE: f32 = 500.0; // Distance at which the sword stops in mid-air
+const RETURN_SPEED: f32 = 1100.0; // px/s on the way back
+const CATCH_DISTANCE: f32 = 30.0; // Sword re-attaches once this close to its node
+pub const EMBED_SOUND: &str = "audio/impact_stone.ogg";
+
+pub struct SwordThrowPlugin;
+
+impl Plugin for SwordThrowPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (handle_throw_input, handle_throw_collisions, update_thrown_swords).chain(),
+        );
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ThrowState {
+    Outbound,  // Flying towards the cursor
+    Embedded,  // Stuck in a wall or resting at max range, waiting for recall
+    Returning, // Homing back to the node
+}
+
+// Present while a sword is detached from its node as a projectile.
+// The sword's SwingAnimation is left untouched and resumes when it is caught.
+#[derive(Component)]
+pub struct ThrownSword {
+    pub state: ThrowState,
+    pub node: Entity,   // Node to return to
+    pub origin: Vec2,   // World position the throw started from
+    pub max_range: f32,
+}
+
+fn handle_throw_input(
+    mut commands: Commands,
+    actions: Res<ActionState>,
+    player_query: Query<&Transform, With<PlayerMovement>>,
+    node_query: Query<(Entity, &Children, &Transform), With<SwordNode>>,
+    mut sword_query: Query<
+        (&Sword, &SwingAnimation, &GlobalTransform, &mut HitRegistry),
+        Without<ThrownSword>,
+    >,
+    mut thrown_query: Query<(&mut ThrownSword, &mut HitRegistry)>,
+) {
+    if !actions.just_pressed(Action::Throw) {
+        return;
+    }
+
+    for (node_entity, children, node_transform) in node_query.iter() {
+        // A second press recalls everything this node has thrown
+        let mut recalled = false;
+        for (mut thrown, mut hits) in thrown_query.iter_mut() {
+            if thrown.node == node_entity && thrown.state != ThrowState::Returning {
+                thrown.state = ThrowState::Returning;
+                // Enemies can be hit again on the return trip
+                hits.clear();
+                recalled = true;
+            }
+        }
+        if recalled {
+            continue;
+        }
+
+        // Otherwise throw the first idle sword in formation order
+        let Some((_, sword_entity)) = children
+            .iter()
+            .filter_map(|child| {
+                let (sword, swing, _, _) = sword_query.get(child).ok()?;
+                (!swing.is_swinging).then_some((sword.slot, child))
+            })
+            .min()
+        else {
+            continue;
+        };
+        let Ok((_, _, global_transform, mut hits)) = sword_query.get_mut(sword_entity) else {
+            continue;
+        };
+
+        let world_transform = global_transform.compute_transform();
+        let origin = world_transform.translation.truncate();
+
+        // Aim from the player towards the node, which sits on the cursor or right-stick aim,
+        // falling back to the sword's own position
+        let from = player_query
+            .single()
+            .map(|t| t.translation.truncate())
+            .unwrap_or(origin);
+        let target = node_transform.translation.truncate();
+        let direction = (target - from).normalize_or(Vec2::Y);
+
+        hits.clear();
+        commands
+            .entity(sword_entity)
+            .remove::<ChildOf>()
+            .insert((
+                world_transform,
+                RigidBody::KinematicVelocityBased,
+                Velocity {
+                    linvel: direction * THROW_SPEED,
+                    angvel: THROW_SPIN,
+                },
+                ThrownSword {
+                    state: ThrowState::Outbound,
+                    node: node_entity,
+                    origin,
+                    max_range: THROW_MAX_RANGE,
+                },
+            ));
+    }
+}
+
+// Embed outbound swords in any wall they touch
+fn handle_throw_collisions(
+    mut collision_events: EventReader<CollisionEvent>,
+    wall_query: Query<(), With<Wall>>,
+    mut thrown_query: Query<(&mut ThrownSword, &mut Velocity)>,
+    mut sound_events: EventWriter<SoundEvent>,
+) {
+    for collision_event in collision_events.read() {
+        let CollisionEvent::Started(entity1, entity2, _) = collision_event else {
+            continue;
+        };
+        let sword_entity = if wall_query.contains(*entity2) {
+            *entity1
+        } else if wall_query.contains(*entity1) {
+            *entity2
+        } else {
+            continue;
+        };
+
+        let Ok((mut thrown, mut velocity)) = thrown_query.get_mut(sword_entity) else {
+            continue;
+        };
+        if thrown.state == ThrowState::Outbound {
+            thrown.state = ThrowState::Embedded;
+            *velocity = Velocity::zero();
+            sound_events.write(SoundEvent::new(EMBED_SOUND).with_volume(0.7));
+        }
+    }
+}
+
+fn update_thrown_swords(
+    mut commands: Commands,
+    node_query: Query<&GlobalTransform, With<SwordNode>>,
+    mut thrown_query: Query<(Entity, &mut ThrownSword, &mut Velocity, &Transform)>,
+) {
+    for (sword_entity, mut thrown, mut velocity, transform) in thrown_query.iter_mut() {
+        let position = transform.translation.truncate();
+
+        match thrown.state {
+            ThrowState::Outbound => {
+                if position.distance(thrown.origin) >= thrown.max_range {
+                    thrown.state = ThrowState::Embedded;
+                    *velocity = Velocity::zero();
+                }
+            }
+            ThrowState::Embedded => {}
+            ThrowState::Returning => {
+                let Ok(node_transform) = node_query.get(thrown.node) else {
+                    // Node is gone, nothing to return to
+                    commands.entity(sword_entity).despawn();
+                    continue;
+                };
+                let node_pos = node_transform.translation().truncate();
+                let to_node = node_pos - position;
+
+                if to_node.length() <= CATCH_DISTANCE {
+                    // Caught: hand the sword back to its node and the swing animation.
+                    // Keep the current offset so the idle pose eases in instead of popping.
+                    commands
+                        .entity(sword_entity)
+                        .remove::<(ThrownSword, Velocity)>()
+                        .insert((
+                            Transform::from_translation((-to_node).extend(1.0))
+                                .with_rotation(transform.rotation),
+                            RigidBody::KinematicPositionBased,
+                            ChildOf(thrown.node),
+                        ));
+                } else {
+                    velocity.linvel = to_node.normalize() * RETURN_SPEED;
+                    velocity.angvel = -THROW_SPIN;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use crate::swing_animation::{SwingAnimation, SwingPhase};
+use bevy::prelude::*;
+
+const SWING_TRAIL_LIFETIME: f32 = 0.15; // Seconds each swing afterimage stays visible
+
+pub struct TrailPlugin;
+
+impl Plugin for TrailPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (emit_swing_trail, fade_afterimages));
+    }
+}
+
+// A fading copy of a sprite left behind by something moving fast
+#[derive(Component)]
+pub struct Afterimage {
+    pub timer: Timer,
+    pub start_alpha: f32,
+    pub color: Color,
+}
+
+// Leave a ghost of `sprite` at `transform` (world space) that fades out over `lifetime`
+pub fn spawn_afterimage(
+    commands: &mut Commands,
+    sprite: &Sprite,
+    transform: Transform,
+    color: Color,
+    alpha: f32,
+    lifetime: f32,
+) {
+    commands.spawn((
+        Sprite {
+            color: color.with_alpha(alpha),
+            ..sprite.clone()
+        },
+        // Draw just behind whatever left it
+        transform.with_translation(transform.translation - Vec3::Z * 0.1),
+        Afterimage {
+            timer: Timer::from_seconds(lifetime, TimerMode::Once),
+            start_alpha: alpha,
+            color,
+        },
+    ));
+}
+
+// Mesh version of spawn_afterimage for entities drawn with a ColorMaterial, like the player
+pub fn spawn_mesh_afterimage(
+    commands: &mut Commands,
+    materials: &mut Assets<ColorMaterial>,
+    mesh: &Mesh2d,
+    transform: Transform,
+    color: Color,
+    alpha: f32,
+    lifetime: f32,
+) {
+    commands.spawn((
+        mesh.clone(),
+        MeshMaterial2d(materials.add(color.with_alpha(alpha))),
+        transform.with_translation(transform.translation - Vec3::Z * 0.1),
+        Afterimage {
+            timer: Timer::from_seconds(lifetime, TimerMode::Once),
+            start_alpha: alpha,
+            color,
+        },
+    ));
+}
+
+// While a sword is in its main swing, leave afterimages along the blade's path.
+// Charged swings leave a brighter trail.
+fn emit_swing_trail(
+    mut commands: Commands,
+    sword_query: Query<(&SwingAnimation, &Sprite, &GlobalTransform)>,
+) {
+    for (swing, sprite, global_transform) in sword_query.iter() {
+        if !swing.is_swinging || !matches!(swing.current_phase, SwingPhase::Swing) {
+            continue;
+        }
+
+        spawn_afterimage(
+            &mut commands,
+            sprite,
+            global_transform.compute_transform(),
+            Color::srgb(0.8, 0.9, 1.0),
+            swing.trail_intensity(),
+            SWING_TRAIL_LIFETIME,
+        );
+    }
+}
+
+fn fade_afterimages(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut afterimage_query: Query<(
+        Entity,
+        &mut Afterimage,
+        Option<&mut Sprite>,
+        Option<&MeshMaterial2d<ColorMaterial>>,
+    )>,
+) {
+    for (entity, mut afterimage, sprite, material) in afterimage_query.iter_mut() {
+        afterimage.timer.tick(time.delta());
+        if afterimage.timer.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let color = afterimage
+            .color
+            .with_alpha(afterimage.start_alpha * afterimage.timer.fraction_remaining());
+        if let Some(mut sprite) = sprite {
+            sprite.color = color;
+        }
+        if let Some(material) = material.and_then(|m| materials.get_mut(&m.0)) {
+            material.color = color;
+        }
+    }
+}
@@ -0,0 +1,559 @@
+use crate::audio::SoundEvent;
+use crate::curve::lerp;
+use crate::damage::{DamageEvent, DamageType, Resistances};
+use crate::enemy::{Enemy, Staggered, ENEMY_LINEAR_DAMPING};
+use crate::input_map::{Action, ActionState};
+use crate::player::PlayerHealth;
+use crate::swing_animation::{SwingAnimation, SwingType};
+use crate::sword::Sword;
+use crate::sword_sprite::{FrameSequence, SwordAnimations, SwordFrames};
+use crate::sword_collider::setup_physics;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Weapon definitions override the built-in ones from here, one file per weapon
+pub const WEAPON_ASSET_DIR: &str = "assets/weapons";
+
+pub const SWAP_SOUND: &str = "audio/weapon_swap.ogg";
+
+// Blade speed (units per second) at which a cut deals its listed damage
+const REFERENCE_BLADE_SPEED: f32 = 1500.0;
+const MIN_SPEED_MULTIPLIER: f32 = 0.5; // A blade at rest still hurts a little
+const MAX_SPEED_MULTIPLIER: f32 = 1.5;
+const HILT_MULTIPLIER: f32 = 0.6; // At the hilt end, rising linearly towards the tip
+
+pub struct WeaponPlugin;
+
+impl Plugin for WeaponPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Weapons::load_or_default())
+            .add_systems(Startup, load_weapon_sprites)
+            .add_systems(
+                Update,
+                (
+                    cycle_weapons,
+                    // Replaces the placeholder collider setup_physics gives new swords
+                    equip_weapons.after(setup_physics),
+                    apply_weapon_specials,
+                )
+                    .chain(),
+            );
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, Serialize, Deserialize)]
+pub enum WeaponKind {
+    Greatsword,
+    Dagger,
+    #[default]
+    Katana,
+    Spear,
+}
+
+impl WeaponKind {
+    pub const ALL: [WeaponKind; 4] = [
+        WeaponKind::Greatsword,
+        WeaponKind::Dagger,
+        WeaponKind::Katana,
+        WeaponKind::Spear,
+    ];
+
+    // File name of the weapon's definition in WEAPON_ASSET_DIR
+    pub fn asset_path(&self) -> PathBuf {
+        let name = match self {
+            WeaponKind::Greatsword => "greatsword",
+            WeaponKind::Dagger => "dagger",
+            WeaponKind::Katana => "katana",
+            WeaponKind::Spear => "spear",
+        };
+        PathBuf::from(WEAPON_ASSET_DIR).join(format!("{}.weapon.ron", name))
+    }
+
+    pub fn next(&self) -> WeaponKind {
+        let index = WeaponKind::ALL.iter().position(|k| k == self).unwrap_or(0);
+        WeaponKind::ALL[(index + 1) % WeaponKind::ALL.len()]
+    }
+}
+
+// Sprite sheet of a weapon; the blade points up (+Y) in every cell
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WeaponSprite {
+    pub texture: String, // Relative to the assets folder
+    pub cell_size: UVec2,
+    pub columns: u32,
+    pub rows: u32,
+    pub size: Vec2, // Drawn size in world units
+    #[serde(default)]
+    pub animations: SwordAnimations,
+}
+
+impl WeaponSprite {
+    pub fn frame_count(&self) -> usize {
+        (self.columns * self.rows) as usize
+    }
+}
+
+// Blade hitbox, centred on the sword's origin
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum WeaponCollider {
+    Cuboid { half_extents: Vec2 },
+    Capsule { half_length: f32, radius: f32 }, // Along the blade
+}
+
+impl WeaponCollider {
+    // Distance from the sword's origin to the tip
+    pub fn half_length(&self) -> f32 {
+        match *self {
+            WeaponCollider::Cuboid { half_extents } => half_extents.y,
+            WeaponCollider::Capsule {
+                half_length,
+                radius,
+            } => half_length + radius,
+        }
+    }
+
+    pub fn collider(&self) -> Collider {
+        match *self {
+            WeaponCollider::Cuboid { half_extents } => {
+                Collider::cuboid(half_extents.x, half_extents.y)
+            }
+            WeaponCollider::Capsule {
+                half_length,
+                radius,
+            } => Collider::capsule_y(half_length, radius),
+        }
+    }
+}
+
+// Extra effect of every landed cut
+#[derive(Clone, Copy, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub enum WeaponSpecial {
+    #[default]
+    None,
+    Stagger { duration: f32 }, // The target can't move or attack for a while
+    Lifesteal { fraction: f32 }, // Heals the player by this share of the damage dealt
+    Execute { threshold: f32 }, // Finishes off targets left below this share of max health
+    Knockback { distance: f32 }, // Shoves the target along the cut
+}
+
+// How much it matters where and how a cut lands
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StrikeStats {
+    pub crit_chance: f32, // 0.0 - 1.0, rolled per hit
+    pub crit_multiplier: f32,
+    pub sweet_spot: f32, // Blade position (0.0 hilt, 1.0 tip) where the sweet spot begins
+    pub sweet_spot_multiplier: f32,
+}
+
+impl Default for StrikeStats {
+    fn default() -> Self {
+        Self {
+            crit_chance: 0.05,
+            crit_multiplier: 2.0,
+            sweet_spot: 0.75,
+            sweet_spot_multiplier: 1.3,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WeaponDef {
+    pub sprite: WeaponSprite,
+    pub collider: WeaponCollider,
+    pub damage: f32, // Per cut, before the charge multiplier
+    #[serde(default)]
+    pub damage_type: DamageType,
+    pub reach: f32,  // Scale of the swing paths, see SwingAnimation::reach
+    pub speed: f32,  // Swing playback multiplier, 1.0 = as authored
+    pub moveset: Vec<SwingType>, // Played in order, one per swing, then repeated
+    #[serde(default)]
+    pub special: WeaponSpecial,
+    #[serde(default)]
+    pub strike: StrikeStats,
+}
+
+impl WeaponDef {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        ron::from_str(&text).map_err(|e| e.to_string())
+    }
+}
+
+// Definitions of every weapon; a resource so they can be tuned at runtime
+#[derive(Resource)]
+pub struct Weapons {
+    pub greatsword: WeaponDef,
+    pub dagger: WeaponDef,
+    pub katana: WeaponDef,
+    pub spear: WeaponDef,
+}
+
+impl Default for Weapons {
+    // Until weapons get their own art they all share the sword sheet, drawn at their size.
+    // Its frames: 0 plain, 1-2 shimmer, 3 glint, 4-5 light and heavy smear.
+    fn default() -> Self {
+        let sprite = |size: Vec2, animations: SwordAnimations| WeaponSprite {
+            texture: "sword_sheet.png".to_string(),
+            cell_size: UVec2::splat(64),
+            columns: 6,
+            rows: 1,
+            size,
+            animations,
+        };
+        // A shimmer now and then at rest, a glint at the top of the windup
+        let animations = |idle_fps: f32, swing: &[usize]| SwordAnimations {
+            idle: FrameSequence::looping(&[0, 0, 0, 0, 0, 0, 1, 2], idle_fps),
+            startup: FrameSequence::timeline(&[0, 0, 3]),
+            swing: FrameSequence::timeline(swing),
+            recovery: FrameSequence::timeline(&[4, 0, 0]),
+        };
+        Self {
+            // Slow and heavy, wide arcs that knock the fight out of whatever they hit
+            greatsword: WeaponDef {
+                sprite: sprite(Vec2::new(80.0, 96.0), animations(6.0, &[3, 4, 5, 5, 5, 4])),
+                collider: WeaponCollider::Cuboid {
+                    half_extents: Vec2::new(14.0, 44.0),
+                },
+                damage: 90.0,
+                damage_type: DamageType::Fire,
+                reach: 1.25,
+                speed: 0.7,
+                moveset: vec![SwingType::Vertical, SwingType::Horizontal],
+                special: WeaponSpecial::Stagger { duration: 0.6 },
+                // Rarely crits, but crushes with the broad upper blade
+                strike: StrikeStats {
+                    crit_chance: 0.05,
+                    crit_multiplier: 2.5,
+                    sweet_spot: 0.6,
+                    sweet_spot_multiplier: 1.5,
+                },
+            },
+            // Quick, short stabs that feed the player
+            dagger: WeaponDef {
+                sprite: sprite(Vec2::new(40.0, 44.0), animations(12.0, &[5, 5, 4])),
+                collider: WeaponCollider::Cuboid {
+                    half_extents: Vec2::new(7.0, 18.0),
+                },
+                damage: 25.0,
+                damage_type: DamageType::Poison,
+                reach: 0.7,
+                speed: 1.6,
+                moveset: vec![SwingType::Thrust, SwingType::Thrust, SwingType::Horizontal],
+                special: WeaponSpecial::Lifesteal { fraction: 0.1 },
+                // Finds the gaps often; too short for the tip to matter much
+                strike: StrikeStats {
+                    crit_chance: 0.25,
+                    crit_multiplier: 1.75,
+                    sweet_spot: 0.8,
+                    sweet_spot_multiplier: 1.2,
+                },
+            },
+            // The original sword, arcing lightning between packed enemies
+            katana: WeaponDef {
+                sprite: sprite(Vec2::splat(64.0), animations(8.0, &[4, 5, 5, 4])),
+                collider: WeaponCollider::Cuboid {
+                    half_extents: Vec2::new(10.0, 30.0),
+                },
+                damage: 50.0,
+                damage_type: DamageType::Lightning,
+                reach: 1.0,
+                speed: 1.0,
+                moveset: vec![SwingType::Vertical, SwingType::Horizontal],
+                special: WeaponSpecial::Execute { threshold: 0.15 },
+                strike: StrikeStats {
+                    crit_chance: 0.15,
+                    crit_multiplier: 2.0,
+                    sweet_spot: 0.7,
+                    sweet_spot_multiplier: 1.3,
+                },
+            },
+            // Long thin shaft, mostly thrusts that push enemies back
+            spear: WeaponDef {
+                sprite: sprite(Vec2::new(40.0, 110.0), animations(8.0, &[4, 4, 5, 0])),
+                collider: WeaponCollider::Capsule {
+                    half_length: 45.0,
+                    radius: 6.0,
+                },
+                damage: 40.0,
+                damage_type: DamageType::Ice,
+                reach: 1.4,
+                speed: 0.9,
+                moveset: vec![SwingType::Thrust, SwingType::Thrust, SwingType::Vertical],
+                special: WeaponSpecial::Knockback { distance: 40.0 },
+                // All in the point
+                strike: StrikeStats {
+                    crit_chance: 0.1,
+                    crit_multiplier: 2.0,
+                    sweet_spot: 0.85,
+                    sweet_spot_multiplier: 1.75,
+                },
+            },
+        }
+    }
+}
+
+impl Weapons {
+    pub fn get(&self, kind: WeaponKind) -> &WeaponDef {
+        match kind {
+            WeaponKind::Greatsword => &self.greatsword,
+            WeaponKind::Dagger => &self.dagger,
+            WeaponKind::Katana => &self.katana,
+            WeaponKind::Spear => &self.spear,
+        }
+    }
+
+    pub fn get_mut(&mut self, kind: WeaponKind) -> &mut WeaponDef {
+        match kind {
+            WeaponKind::Greatsword => &mut self.greatsword,
+            WeaponKind::Dagger => &mut self.dagger,
+            WeaponKind::Katana => &mut self.katana,
+            WeaponKind::Spear => &mut self.spear,
+        }
+    }
+
+    // Built-in definitions, overridden by any weapon asset that exists
+    pub fn load_or_default() -> Self {
+        let mut weapons = Weapons::default();
+        for kind in WeaponKind::ALL {
+            let path = kind.asset_path();
+            if !path.exists() {
+                continue;
+            }
+            match WeaponDef::load(&path) {
+                Ok(def) => *weapons.get_mut(kind) = def,
+                Err(error) => {
+                    let path = path.display();
+                    warn!(target: "weapon", "Failed to load weapon asset {}: {}", path, error);
+                }
+            }
+        }
+        weapons
+    }
+}
+
+// Texture and atlas layout handles per weapon, so swapping doesn't reload anything
+#[derive(Resource, Default)]
+pub struct WeaponSprites {
+    handles: HashMap<WeaponKind, (Handle<Image>, Handle<TextureAtlasLayout>)>,
+}
+
+impl WeaponSprites {
+    pub fn get(&self, kind: WeaponKind) -> Option<&(Handle<Image>, Handle<TextureAtlasLayout>)> {
+        self.handles.get(&kind)
+    }
+}
+
+// The weapon a sword carries. Setting `kind` swaps it: equip_weapons rebuilds the sword's
+// sprite, collider and swing to match on the next frame.
+#[derive(Component, Default)]
+pub struct Weapon {
+    pub kind: WeaponKind,
+    equipped: Option<WeaponKind>,
+    // Copied from the definition on equip
+    pub damage: f32,
+    pub damage_type: DamageType,
+    pub special: WeaponSpecial,
+    pub strike: StrikeStats,
+    pub blade_half_length: f32,
+    moveset: Vec<SwingType>,
+    combo: usize, // Next move of the moveset
+}
+
+// Where and how fast the blade connected
+#[derive(Clone, Copy, Debug)]
+pub struct BladeContact {
+    pub along: f32, // 0.0 at the hilt end, 1.0 at the tip
+    pub speed: f32, // Units per second
+}
+
+pub struct WeaponHit {
+    pub amount: f32,
+    pub crit: bool,
+    pub sweet_spot: bool,
+}
+
+impl Weapon {
+    pub fn new(kind: WeaponKind) -> Self {
+        Self {
+            kind,
+            ..default()
+        }
+    }
+
+    // Swing type of the next attack, advancing through the moveset
+    pub fn next_swing(&mut self) -> SwingType {
+        if self.moveset.is_empty() {
+            return SwingType::Vertical;
+        }
+        let swing_type = self.moveset[self.combo % self.moveset.len()];
+        self.combo = (self.combo + 1) % self.moveset.len();
+        swing_type
+    }
+
+    // A cut on `enemy` landing at `contact`, with the swing's charge multiplier applied and
+    // `crit_roll` (0.0 - 1.0) deciding the crit. Resistances are applied later by
+    // apply_damage; an execute is sized to get through them.
+    pub fn hit(
+        &self,
+        enemy: &Enemy,
+        resistances: Option<&Resistances>,
+        contact: BladeContact,
+        multiplier: f32,
+        crit_roll: f32,
+    ) -> WeaponHit {
+        let along = contact.along.clamp(0.0, 1.0);
+        let sweet_spot = along >= self.strike.sweet_spot;
+        let crit = crit_roll < self.strike.crit_chance;
+
+        let speed = (contact.speed / REFERENCE_BLADE_SPEED)
+            .clamp(MIN_SPEED_MULTIPLIER, MAX_SPEED_MULTIPLIER);
+        let mut amount = self.damage * multiplier * speed * lerp(HILT_MULTIPLIER, 1.0, along);
+        if sweet_spot {
+            amount *= self.strike.sweet_spot_multiplier;
+        }
+        if crit {
+            amount *= self.strike.crit_multiplier;
+        }
+
+        let resist = resistances.map_or(1.0, |r| r.multiplier(self.damage_type));
+        if let WeaponSpecial::Execute { threshold } = self.special
+            && resist > 0.0
+            && enemy.health - amount * resist < enemy.max_health * threshold
+        {
+            amount = amount.max(enemy.health / resist);
+        }
+        WeaponHit {
+            amount,
+            crit,
+            sweet_spot,
+        }
+    }
+}
+
+fn load_weapon_sprites(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    weapons: Res<Weapons>,
+) {
+    let mut sprites = WeaponSprites::default();
+    for kind in WeaponKind::ALL {
+        let sprite = &weapons.get(kind).sprite;
+        let texture = asset_server.load(sprite.texture.clone());
+        let layout = TextureAtlasLayout::from_grid(
+            sprite.cell_size,
+            sprite.columns,
+            sprite.rows,
+            None,
+            None,
+        );
+        sprites.handles.insert(kind, (texture, texture_atlas_layouts.add(layout)));
+    }
+    commands.insert_resource(sprites);
+}
+
+// Every sword switches to the next weapon
+fn cycle_weapons(
+    actions: Res<ActionState>,
+    mut weapon_query: Query<&mut Weapon>,
+    mut sound_events: EventWriter<SoundEvent>,
+) {
+    if !actions.just_pressed(Action::CycleWeapon) {
+        return;
+    }
+    for mut weapon in weapon_query.iter_mut() {
+        weapon.kind = weapon.kind.next();
+    }
+    if !weapon_query.is_empty() {
+        sound_events.write(SoundEvent::new(SWAP_SOUND));
+    }
+}
+
+// Rebuild swords whose weapon changed, including newly spawned ones
+fn equip_weapons(
+    mut commands: Commands,
+    weapons: Res<Weapons>,
+    sprites: Res<WeaponSprites>,
+    mut sword_query: Query<(Entity, &mut Weapon, &mut Sprite, &mut SwingAnimation), With<Sword>>,
+) {
+    for (entity, mut weapon, mut sprite, mut swing) in sword_query.iter_mut() {
+        if weapon.equipped == Some(weapon.kind) {
+            continue;
+        }
+        let kind = weapon.kind;
+        let def = weapons.get(kind);
+
+        if let Some((texture, layout)) = sprites.get(kind) {
+            sprite.image = texture.clone();
+            sprite.texture_atlas = Some(TextureAtlas {
+                layout: layout.clone(),
+                index: 0,
+            });
+        }
+        sprite.custom_size = Some(def.sprite.size);
+        let frames = SwordFrames::new(def.sprite.animations.clone(), def.sprite.frame_count());
+        commands.entity(entity).insert((def.collider.collider(), frames));
+
+        // A swing in progress belongs to the old weapon; the new one starts from rest
+        swing.cancel();
+        swing.reach = def.reach;
+        swing.time_scale.weapon = def.speed;
+
+        weapon.equipped = Some(kind);
+        weapon.damage = def.damage;
+        weapon.damage_type = def.damage_type;
+        weapon.special = def.special;
+        weapon.strike = def.strike;
+        weapon.blade_half_length = def.collider.half_length();
+        weapon.moveset = def.moveset.clone();
+        weapon.combo = 0;
+        info!(target: "weapon", sword = ?entity, ?kind, "equipped");
+    }
+}
+
+// Per-weapon effects of cuts that landed
+fn apply_weapon_specials(
+    mut commands: Commands,
+    mut damage_events: EventReader<DamageEvent>,
+    weapon_query: Query<&Weapon>,
+    mut enemy_query: Query<(&mut Velocity, Option<&Resistances>), With<Enemy>>,
+    mut player_query: Query<&mut PlayerHealth>,
+) {
+    for event in damage_events.read() {
+        if event.indirect {
+            continue;
+        }
+        let Some(weapon) = event.source.and_then(|source| weapon_query.get(source).ok()) else {
+            continue;
+        };
+        let Ok((mut velocity, resistances)) = enemy_query.get_mut(event.target) else {
+            continue;
+        };
+        match weapon.special {
+            WeaponSpecial::None | WeaponSpecial::Execute { .. } => {}
+            WeaponSpecial::Stagger { duration } => {
+                // The target may have died and been despawned already
+                commands.entity(event.target).try_insert(Staggered {
+                    timer: Timer::from_seconds(duration, TimerMode::Once),
+                });
+            }
+            WeaponSpecial::Lifesteal { fraction } => {
+                // Only what got through the target's resistances counts as dealt
+                let dealt = event.resisted_amount(resistances);
+                if let Ok(mut health) = player_query.single_mut() {
+                    health.current = (health.current + dealt * fraction).min(health.max);
+                }
+            }
+            WeaponSpecial::Knockback { distance } => {
+                // Launched rather than moved, so the physics keeps it out of the walls; the
+                // damping brings it to rest about `distance` away
+                velocity.linvel = event.direction * distance * ENEMY_LINEAR_DAMPING;
+            }
+        }
+    }
+}